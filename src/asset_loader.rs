@@ -1,6 +1,7 @@
 use bevy::prelude::*;
 //use bevy_rapier2d::prelude::*;
 
+use crate::materials::{read_materials, MaterialRegistry};
 use crate::state_system::AppState;
 
 #[derive(Bundle)]
@@ -28,13 +29,14 @@ impl Plugin for AssetLoaderPlugin {
 #[derive(Resource, Debug, Default)]
 struct LoadingAssets {
     image_handles: Vec<Handle<Image>>,
+    /// the materials file, with its textures loading
+    materials: MaterialRegistry,
 }
 
 #[derive(Resource, Debug, Default)]
 pub struct SceneAsset {
     pub t_temp: Handle<Image>,
     pub t_scorch: Handle<Image>,
-    pub t_block: Handle<Image>,
     pub t_ember: Handle<Image>,
     pub t_enemy: Handle<Image>,
     pub t_enemy_p: Handle<Image>,
@@ -43,8 +45,10 @@ pub struct SceneAsset {
     pub t_enemy4: Handle<Image>,
     pub t_enemy5: Handle<Image>,
     pub t_enemy6: Handle<Image>,
+    /// doors and platforms
     pub t_block_unburnable: Handle<Image>,
-    pub t_block_insta_burn: Handle<Image>,
+    /// what blocks are made of, their textures come from the materials file
+    pub materials: MaterialRegistry,
 }
 
 fn load_assets(
@@ -58,7 +62,7 @@ fn load_assets(
 
     let mut all_loaded = true;
 
-    for handle in &loading_assets.image_handles {
+    for handle in loading_assets.image_handles.iter().chain(loading_assets.materials.textures.values()) {
         match asset_server.get_load_state(handle) {
             Some(LoadState::Loaded) => continue, // This asset is loaded; check the next one
             Some(LoadState::Failed(_)) => {
@@ -85,23 +89,22 @@ fn load_assets(
         scene_assets.t_temp = loading_assets.image_handles[0].clone();
         scene_assets.t_scorch = loading_assets.image_handles[1].clone();
         scene_assets.t_ember = loading_assets.image_handles[2].clone();
-        scene_assets.t_block = loading_assets.image_handles[3].clone();
-        scene_assets.t_block_unburnable = loading_assets.image_handles[4].clone();
-        scene_assets.t_block_insta_burn = loading_assets.image_handles[5].clone();
-        scene_assets.t_enemy = loading_assets.image_handles[6].clone();
-        scene_assets.t_enemy_p = loading_assets.image_handles[7].clone();
-        scene_assets.t_enemy2 = loading_assets.image_handles[8].clone();
-        scene_assets.t_enemy3 = loading_assets.image_handles[9].clone();
-        scene_assets.t_enemy4 = loading_assets.image_handles[10].clone();
-        scene_assets.t_enemy5 = loading_assets.image_handles[11].clone();
-        scene_assets.t_enemy6 = loading_assets.image_handles[12].clone();
+        scene_assets.t_block_unburnable = loading_assets.image_handles[3].clone();
+        scene_assets.t_enemy = loading_assets.image_handles[4].clone();
+        scene_assets.t_enemy_p = loading_assets.image_handles[5].clone();
+        scene_assets.t_enemy2 = loading_assets.image_handles[6].clone();
+        scene_assets.t_enemy3 = loading_assets.image_handles[7].clone();
+        scene_assets.t_enemy4 = loading_assets.image_handles[8].clone();
+        scene_assets.t_enemy5 = loading_assets.image_handles[9].clone();
+        scene_assets.t_enemy6 = loading_assets.image_handles[10].clone();
+        scene_assets.materials = loading_assets.materials.clone();
         //scene_assets.t_ = loading_assets.image_handles[7].clone();
         
         // the loading assets is now redundent and less organgized compared to the scene assets
         commands.remove_resource::<LoadingAssets>();
 
         //transition the state for next frame
-        next_state.set(AppState::InGame);
+        next_state.set(AppState::MainMenu);
     }
 }
 
@@ -113,9 +116,7 @@ fn preload_textures(
         asset_server.load("sprites/t_temp.png"),
         asset_server.load("sprites/t_scorch.png"),
         asset_server.load("sprites/t_ember.png"),
-        asset_server.load("sprites/t_wood_brown.png"),
         asset_server.load("sprites/t_slate.png"),
-        asset_server.load("sprites/t_paper.png"),
         asset_server.load("sprites/t_enemy.png"),
         asset_server.load("sprites/t_enemy_p.png"),
         asset_server.load("sprites/t_enemy2.png"),
@@ -126,4 +127,16 @@ fn preload_textures(
         //asset_server.load("sprites/t_.png"),
     ];
     handle_res.image_handles = texture_handles;
+
+    // the block textures come from the materials file
+    let mut materials = read_materials().unwrap_or_else(|err| {
+        // every block is drawn plain white and made of the fallback material
+        println!("ERROR: {}", err);
+        MaterialRegistry::default()
+    });
+    materials.textures = materials.materials
+        .iter()
+        .map(|(name, material)| (name.clone(), asset_server.load(material.texture.clone())))
+        .collect();
+    handle_res.materials = materials;
 }
\ No newline at end of file
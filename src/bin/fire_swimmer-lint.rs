@@ -0,0 +1,695 @@
+// Checks level files for mistakes that would otherwise only be found by playing them.
+// usage: fire_swimmer-lint [level.json | map.tmj ...]
+// with no arguments every level in the level manifest is checked, the materials file is always checked.
+// exits with 1 if any level has errors, warnings do not change the exit code.
+
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::process::ExitCode;
+
+use fire_swimmer::blocks::BlockShape;
+use fire_swimmer::heat::AMBIENT_TEMP;
+use fire_swimmer::level_format::{
+    level_version, parse_level, BlockData, BoundsData, EnemyData, LevelData, SwitchData, TriggerAction,
+    TriggerData, LEVEL_VERSION,
+};
+use fire_swimmer::materials::{read_materials, MaterialRegistry, MATERIALS_FILE};
+use fire_swimmer::sd_to_game::read_level_manifest;
+use fire_swimmer::tiled::parse_tiled_map;
+
+/// the manifest paths are relative to this
+const ASSET_FOLDER: &str = "assets";
+
+/// touching is fine, overlapping by less than this is also let through
+const OVERLAP_TOLERANCE: f32 = 0.01;
+
+/// the size of the collider scorch is spawned with
+const SCORCH_RADIUS: f32 = 50.0;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Severity {
+    Warning,
+    Error,
+}
+
+struct Issue {
+    severity: Severity,
+    /// which entry in the level the issue is about, like blocks[3]
+    location: String,
+    message: String,
+}
+
+impl Issue {
+    fn error(location: String, message: String) -> Self {
+        Issue { severity: Severity::Error, location, message }
+    }
+
+    fn warning(location: String, message: String) -> Self {
+        Issue { severity: Severity::Warning, location, message }
+    }
+}
+
+fn main() -> ExitCode {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+
+    let paths: Vec<PathBuf> = if args.is_empty() {
+        match read_level_manifest() {
+            Ok(levels) => levels
+                .iter()
+                .map(|entry| Path::new(ASSET_FOLDER).join(&entry.path))
+                .collect(),
+            Err(err) => {
+                eprintln!("error: {}", err);
+                return ExitCode::FAILURE;
+            }
+        }
+    } else {
+        args.iter().map(PathBuf::from).collect()
+    };
+
+    // every level needs the materials to know what its blocks are
+    let materials = match read_materials() {
+        Ok(materials) => materials,
+        Err(err) => {
+            eprintln!("error: {}", err);
+            return ExitCode::FAILURE;
+        }
+    };
+
+    let mut error_count = 0;
+    let mut warning_count = 0;
+    let mut report = |path: &Path, issues: Vec<Issue>| {
+        for issue in issues {
+            let label = match issue.severity {
+                Severity::Error => {
+                    error_count += 1;
+                    "error"
+                }
+                Severity::Warning => {
+                    warning_count += 1;
+                    "warning"
+                }
+            };
+            println!("{}: {}: {}: {}", path.display(), label, issue.location, issue.message);
+        }
+    };
+    report(Path::new(MATERIALS_FILE), lint_materials(&materials));
+    for path in &paths {
+        report(path, lint_file(path, &materials));
+    }
+
+    println!(
+        "checked {} level(s): {} error(s), {} warning(s)",
+        paths.len(), error_count, warning_count
+    );
+    if error_count > 0 {
+        ExitCode::FAILURE
+    } else {
+        ExitCode::SUCCESS
+    }
+}
+
+/// reads and parses one level then runs all the checks on it
+fn lint_file(path: &Path, materials: &MaterialRegistry) -> Vec<Issue> {
+    let display = path.display().to_string();
+    let bytes = match std::fs::read(path) {
+        Ok(bytes) => bytes,
+        Err(err) => return vec![Issue::error(String::from("."), format!("could not read file: {}", err))],
+    };
+
+    // Tiled maps are converted by the game, so only the converted level can be checked
+    if path.extension().is_some_and(|ext| ext == "tmj") {
+        return match parse_tiled_map(&display, &bytes) {
+            Ok(level) => lint_level(&level, materials),
+            Err(err) => vec![Issue::error(String::from("."), err.to_string())],
+        };
+    }
+
+    // some things are checked before the full parse so every problem is listed, not just the first
+    let mut issues = check_raw_level(&display, &bytes, materials);
+    if issues.iter().any(|issue| issue.severity == Severity::Error) {
+        return issues;
+    }
+
+    match parse_level(&display, &bytes) {
+        Ok(level) => issues.extend(lint_level(&level, materials)),
+        Err(err) => issues.push(Issue::error(String::from("."), err.to_string())),
+    }
+    issues
+}
+
+/// checks on the json as written, before it is migrated to the current version
+fn check_raw_level(path: &str, bytes: &[u8], materials: &MaterialRegistry) -> Vec<Issue> {
+    let mut issues = Vec::new();
+    // if this is not json at all the full parse reports where
+    let Ok(value) = serde_json::from_slice::<serde_json::Value>(bytes) else {
+        return issues;
+    };
+    let version = level_version(&value);
+    if version < LEVEL_VERSION as u64 {
+        issues.push(Issue::warning(
+            String::from("version"),
+            format!("level is version {}, it is migrated to version {} when loaded", version, LEVEL_VERSION),
+        ));
+    }
+    // blocks are checked once they are parsed, but a prefab nothing uses would not be
+    if let Some(prefabs) = value.pointer("/prefabs/blocks").and_then(|p| p.as_object()) {
+        for (name, prefab) in prefabs {
+            // texture is what material was called before version 6
+            for field in ["material", "texture"] {
+                let Some(material) = prefab.pointer(&format!("/block_info/{}", field)) else {
+                    continue;
+                };
+                if !material.as_str().is_some_and(|m| materials.contains(m)) {
+                    issues.push(Issue::error(
+                        format!("prefabs.blocks.{}.block_info.{}", name, field),
+                        format!("unknown material {} in {}, it is not in {}", material, path, MATERIALS_FILE),
+                    ));
+                }
+            }
+        }
+    }
+    let Some(blocks) = value.get("blocks").and_then(|b| b.as_array()) else {
+        return issues;
+    };
+    for (i, block) in blocks.iter().enumerate() {
+        // version 1 had the start time in the level, anything but 0.0 there meant it started lit
+        if let Some(start) = block.pointer("/block_info/burn_time/1").and_then(|s| s.as_f64()) {
+            if start != 0.0 {
+                issues.push(Issue::error(
+                    format!("blocks[{}].block_info.burn_time", i),
+                    format!("burn_time start should be 0.0, found {} (it is set when the block is lit)", start),
+                ));
+            }
+        }
+    }
+    issues
+}
+
+/// checks on the materials file itself, locations are the material names
+fn lint_materials(materials: &MaterialRegistry) -> Vec<Issue> {
+    let mut issues = Vec::new();
+    for (name, material) in &materials.materials {
+        let location = format!("materials.{}", name);
+        let numbers = [
+            ("burn_time", Some(material.burn_time)),
+            ("spread_delay", material.spread_delay),
+            ("friction", Some(material.friction)),
+            ("restitution", Some(material.restitution)),
+        ];
+        for (field, value) in numbers {
+            if let Some(value) = value.filter(|v| *v < 0.0) {
+                issues.push(Issue::error(location.clone(), format!("{} is negative ({})", field, value)));
+            }
+        }
+        if material.density <= 0.0 {
+            issues.push(Issue::error(
+                location.clone(),
+                format!("density must be positive, found {}", material.density),
+            ));
+        }
+        if material.color.iter().any(|c| !(0.0..=1.0).contains(c)) {
+            issues.push(Issue::warning(
+                location.clone(),
+                format!("color should be from 0.0 to 1.0, found {:?}", material.color),
+            ));
+        }
+        if !Path::new(ASSET_FOLDER).join(&material.texture).is_file() {
+            issues.push(Issue::error(
+                location.clone(),
+                format!("texture {} does not exist", material.texture),
+            ));
+        }
+        if material.burnable && material.ignition_temp <= AMBIENT_TEMP {
+            issues.push(Issue::error(
+                location.clone(),
+                format!(
+                    "ignition_temp {} is not above the ambient temperature {}, it would catch fire on its own",
+                    material.ignition_temp, AMBIENT_TEMP
+                ),
+            ));
+        }
+        if material.spread_delay.is_some() && !material.burnable {
+            issues.push(Issue::warning(
+                location,
+                String::from("has a spread_delay but is not burnable, fire never spreads to it"),
+            ));
+        }
+    }
+    issues
+}
+
+fn lint_level(level: &LevelData, materials: &MaterialRegistry) -> Vec<Issue> {
+    let mut issues = Vec::new();
+
+    for (i, block) in level.blocks.iter().enumerate() {
+        lint_block(&format!("blocks[{}]", i), block, materials, &mut issues);
+    }
+    for (i, enemy) in level.enemies.iter().enumerate() {
+        lint_enemy(&format!("enemies[{}]", i), enemy, &mut issues);
+    }
+
+    // overlapping blocks
+    for (i, a) in level.blocks.iter().enumerate() {
+        for (j, b) in level.blocks.iter().enumerate().skip(i + 1) {
+            if blocks_overlap(a, b) {
+                issues.push(Issue::error(
+                    format!("blocks[{}]", i),
+                    format!("overlaps blocks[{}]", j),
+                ));
+            }
+        }
+    }
+
+    // enemies spawned inside blocks
+    for (i, enemy) in level.enemies.iter().enumerate() {
+        for (j, block) in level.blocks.iter().enumerate() {
+            if enemy_in_block(enemy, block) {
+                issues.push(Issue::error(
+                    format!("enemies[{}]", i),
+                    format!("spawns inside blocks[{}]", j),
+                ));
+            }
+        }
+    }
+
+    if let Some(goal) = &level.goal {
+        if goal.size[0] <= 0.0 || goal.size[1] <= 0.0 {
+            issues.push(Issue::error(
+                String::from("goal"),
+                format!("size must be positive, found {:?}", goal.size),
+            ));
+        }
+    }
+
+    for (i, checkpoint) in level.checkpoints.iter().enumerate() {
+        if checkpoint.size[0] <= 0.0 || checkpoint.size[1] <= 0.0 {
+            issues.push(Issue::error(
+                format!("checkpoints[{}]", i),
+                format!("size must be positive, found {:?}", checkpoint.size),
+            ));
+        }
+    }
+
+    let areas = level.pools.iter().enumerate().map(|(i, p)| (format!("pools[{}]", i), p.size))
+        .chain(level.water.iter().enumerate().map(|(i, w)| (format!("water[{}]", i), w.size)))
+        .chain(level.rain.iter().enumerate().map(|(i, r)| (format!("rain[{}]", i), r.size)));
+    for (location, size) in areas {
+        if size[0] <= 0.0 || size[1] <= 0.0 {
+            issues.push(Issue::error(
+                location,
+                format!("size must be positive, found {:?}", size),
+            ));
+        }
+    }
+
+    let mut ids = HashMap::new();
+    for (i, block) in level.blocks.iter().enumerate() {
+        if let Some(id) = &block.id {
+            if let Some(first) = ids.insert(id.as_str(), i) {
+                issues.push(Issue::error(
+                    format!("blocks[{}]", i),
+                    format!("id `{}` is already used by blocks[{}]", id, first),
+                ));
+            }
+        }
+    }
+
+    for (i, trigger) in level.triggers.iter().enumerate() {
+        lint_trigger(&format!("triggers[{}]", i), trigger, &ids, &mut issues);
+    }
+
+    lint_wiring(level, &ids, materials, &mut issues);
+
+    // scorch respawns at the spawn and at checkpoints, it gets stuck if that is inside a block
+    let respawns = std::iter::once((String::from("spawn"), level.spawn.unwrap_or_default()))
+        .chain(level.checkpoints.iter().enumerate().map(|(i, c)| (format!("checkpoints[{}]", i), c.pos)));
+    for (location, pos) in respawns {
+        for (j, block) in level.blocks.iter().enumerate() {
+            if circle_in_block(pos, SCORCH_RADIUS, block) {
+                issues.push(Issue::error(
+                    location.clone(),
+                    format!("scorch would respawn inside blocks[{}]", j),
+                ));
+            }
+        }
+    }
+
+    if let Some(bounds) = &level.bounds {
+        lint_bounds(level, bounds, &mut issues);
+    }
+
+    issues
+}
+
+/// anything outside the bounds is despawned or kills scorch as soon as the level starts
+fn lint_bounds(level: &LevelData, bounds: &BoundsData, issues: &mut Vec<Issue>) {
+    if bounds.min[0] >= bounds.max[0] || bounds.min[1] >= bounds.max[1] {
+        issues.push(Issue::error(
+            String::from("bounds"),
+            format!("min {:?} must be below and left of max {:?}", bounds.min, bounds.max),
+        ));
+        return;
+    }
+    let inside = |pos: [f32; 2]| bounds.to_rect().contains(pos.into());
+
+    if !inside(level.spawn.unwrap_or_default()) {
+        issues.push(Issue::error(String::from("spawn"), String::from("is outside the bounds")));
+    }
+    for (i, checkpoint) in level.checkpoints.iter().enumerate() {
+        if !inside(checkpoint.pos) {
+            issues.push(Issue::error(format!("checkpoints[{}]", i), String::from("is outside the bounds")));
+        }
+    }
+    if let Some(goal) = &level.goal {
+        if !inside(goal.pos) {
+            issues.push(Issue::error(String::from("goal"), String::from("is outside the bounds")));
+        }
+    }
+    for (i, enemy) in level.enemies.iter().enumerate() {
+        if !inside(enemy.pos) {
+            issues.push(Issue::error(format!("enemies[{}]", i), String::from("is outside the bounds")));
+        }
+    }
+    // blocks are fine to stick out, but one that is all the way outside can never be reached
+    for (i, block) in level.blocks.iter().enumerate() {
+        let b_min = [block.pos[0] - block.size[0], block.pos[1] - block.size[1]];
+        let b_max = [block.pos[0] + block.size[0], block.pos[1] + block.size[1]];
+        if
+            b_max[0] < bounds.min[0] || b_min[0] > bounds.max[0]
+            || b_max[1] < bounds.min[1] || b_min[1] > bounds.max[1]
+        {
+            issues.push(Issue::warning(format!("blocks[{}]", i), String::from("is outside the bounds")));
+        }
+    }
+}
+
+fn lint_block(location: &str, block: &BlockData, materials: &MaterialRegistry, issues: &mut Vec<Issue>) {
+    // what the block ends up as, with the material filling in what it leaves out
+    let info = block.block_info.to_block_info(materials);
+
+    if block.size[0] <= 0.0 || block.size[1] <= 0.0 {
+        issues.push(Issue::error(
+            location.to_string(),
+            format!("size must be positive, found {:?}", block.size),
+        ));
+    }
+    if !materials.contains(&info.material) {
+        issues.push(Issue::error(
+            location.to_string(),
+            format!("unknown material `{}`, it is not in {}", info.material, MATERIALS_FILE),
+        ));
+    }
+    if info.burn_time < 0.0 {
+        issues.push(Issue::error(
+            location.to_string(),
+            format!("burn_time is negative ({})", info.burn_time),
+        ));
+    }
+    if info.burnable && !info.extinguishable {
+        issues.push(Issue::warning(
+            location.to_string(),
+            String::from("burnable but not extinguishable, once lit it can not be saved"),
+        ));
+    }
+    if let Some(delay) = info.spread_delay {
+        if delay < 0.0 {
+            issues.push(Issue::error(
+                location.to_string(),
+                format!("spread_delay is negative ({})", delay),
+            ));
+        }
+        if materials.get(&info.material).spread_delay.is_none() {
+            issues.push(Issue::warning(
+                location.to_string(),
+                format!("spread_delay does nothing on {}, fire never spreads to it", info.material),
+            ));
+        }
+    }
+    if info.explosive && !info.burnable {
+        issues.push(Issue::warning(
+            location.to_string(),
+            String::from("explosive but not burnable, it can never go off"),
+        ));
+    }
+    if let BlockShape::Polygon { points } = &block.shape {
+        lint_polygon(location, points, issues);
+    }
+}
+
+/// polygon points are from -1.0 to 1.0 inside the block's box and the shape has to be convex
+fn lint_polygon(location: &str, points: &[[f32; 2]], issues: &mut Vec<Issue>) {
+    if points.len() < 3 {
+        issues.push(Issue::error(
+            location.to_string(),
+            format!("a polygon needs at least 3 points, found {}", points.len()),
+        ));
+        return;
+    }
+    for (i, point) in points.iter().enumerate() {
+        if point.iter().any(|v| v.abs() > 1.0 + OVERLAP_TOLERANCE) {
+            issues.push(Issue::error(
+                location.to_string(),
+                format!("polygon point {} {:?} is outside the block, points go from -1.0 to 1.0", i, point),
+            ));
+        }
+    }
+
+    // the cross product of each pair of edges, they all have the same sign when it is convex
+    let mut area = 0.0;
+    let (mut left, mut right) = (false, false);
+    for i in 0..points.len() {
+        let a = points[i];
+        let b = points[(i + 1) % points.len()];
+        let c = points[(i + 2) % points.len()];
+        let cross = (b[0] - a[0]) * (c[1] - b[1]) - (b[1] - a[1]) * (c[0] - b[0]);
+        if cross > OVERLAP_TOLERANCE {
+            left = true;
+        } else if cross < -OVERLAP_TOLERANCE {
+            right = true;
+        }
+        area += a[0] * b[1] - b[0] * a[1];
+    }
+    if area.abs() / 2.0 < OVERLAP_TOLERANCE {
+        issues.push(Issue::error(
+            location.to_string(),
+            String::from("polygon has no area"),
+        ));
+    } else if left && right {
+        issues.push(Issue::error(
+            location.to_string(),
+            String::from("polygon is not convex, it would be filled in to its hull"),
+        ));
+    }
+}
+
+fn lint_enemy(location: &str, enemy: &EnemyData, issues: &mut Vec<Issue>) {
+    if enemy.size <= 0.0 {
+        issues.push(Issue::error(
+            location.to_string(),
+            format!("size must be positive, found {}", enemy.size),
+        ));
+    }
+    if enemy.e_info.health <= 0.0 {
+        issues.push(Issue::error(
+            location.to_string(),
+            format!("health must be positive, found {}", enemy.e_info.health),
+        ));
+    }
+}
+
+fn lint_trigger(
+    location: &str,
+    trigger: &TriggerData,
+    ids: &HashMap<&str, usize>,
+    issues: &mut Vec<Issue>,
+) {
+    if trigger.size[0] <= 0.0 || trigger.size[1] <= 0.0 {
+        issues.push(Issue::error(
+            location.to_string(),
+            format!("size must be positive, found {:?}", trigger.size),
+        ));
+    }
+    if trigger.actions.is_empty() {
+        issues.push(Issue::warning(location.to_string(), String::from("has no actions")));
+    }
+    for (a, action) in trigger.actions.iter().enumerate() {
+        let action_location = format!("{}.actions[{}]", location, a);
+        match action {
+            TriggerAction::SpawnEnemies { enemies } => {
+                for (i, enemy) in enemies.iter().enumerate() {
+                    lint_enemy(&format!("{}.enemies[{}]", action_location, i), enemy, issues);
+                }
+            }
+            TriggerAction::Hint { duration, .. } => {
+                if *duration <= 0.0 {
+                    issues.push(Issue::error(
+                        action_location,
+                        format!("duration must be positive, found {}", duration),
+                    ));
+                }
+            }
+            TriggerAction::Ignite { blocks } => {
+                for id in blocks {
+                    if !ids.contains_key(id.as_str()) {
+                        issues.push(Issue::error(
+                            action_location.clone(),
+                            format!("there is no block with the id `{}`", id),
+                        ));
+                    }
+                }
+            }
+            TriggerAction::LockCamera { .. } | TriggerAction::UnlockCamera => {}
+        }
+    }
+}
+
+/// switches and gates need unique ids, and everything wired up needs to point at one of them
+fn lint_wiring(
+    level: &LevelData,
+    block_ids: &HashMap<&str, usize>,
+    materials: &MaterialRegistry,
+    issues: &mut Vec<Issue>,
+) {
+    let mut signals: HashMap<&str, String> = HashMap::new();
+    let named = level.switches.iter().enumerate().map(|(i, s)| (s.id(), format!("switches[{}]", i)))
+        .chain(level.logic.iter().enumerate().map(|(i, g)| (g.id(), format!("logic[{}]", i))));
+    for (id, location) in named {
+        if let Some(first) = signals.get(id) {
+            issues.push(Issue::error(location, format!("id `{}` is already used by {}", id, first)));
+        } else {
+            signals.insert(id, location);
+        }
+    }
+
+    let mut check_input = |location: String, input: &str| {
+        if !signals.contains_key(input) {
+            issues.push(Issue::error(location, format!("there is no switch or logic with the id `{}`", input)));
+        }
+    };
+    for (i, gate) in level.logic.iter().enumerate() {
+        for input in gate.inputs() {
+            check_input(format!("logic[{}]", i), input);
+        }
+    }
+    for (i, door) in level.doors.iter().enumerate() {
+        check_input(format!("doors[{}]", i), &door.input);
+    }
+    for (i, platform) in level.platforms.iter().enumerate() {
+        check_input(format!("platforms[{}]", i), &platform.input);
+    }
+    for (i, spawner) in level.spawners.iter().enumerate() {
+        check_input(format!("spawners[{}]", i), &spawner.input);
+    }
+
+    for (i, switch) in level.switches.iter().enumerate() {
+        let location = format!("switches[{}]", i);
+        match switch {
+            SwitchData::Touch { size, .. } | SwitchData::Ember { size, .. } => {
+                if size[0] <= 0.0 || size[1] <= 0.0 {
+                    issues.push(Issue::error(location, format!("size must be positive, found {:?}", size)));
+                }
+            }
+            SwitchData::BlockBurnt { block, .. } => match block_ids.get(block.as_str()) {
+                None => issues.push(Issue::error(location, format!("there is no block with the id `{}`", block))),
+                Some(&b) if !level.blocks[b].block_info.to_block_info(materials).burnable => issues.push(Issue::warning(
+                    location,
+                    format!("blocks[{}] is not burnable, so this switch can never turn on", b),
+                )),
+                Some(_) => {}
+            },
+        }
+    }
+
+    for (i, door) in level.doors.iter().enumerate() {
+        if door.size[0] <= 0.0 || door.size[1] <= 0.0 {
+            issues.push(Issue::error(format!("doors[{}]", i), format!("size must be positive, found {:?}", door.size)));
+        }
+    }
+    for (i, platform) in level.platforms.iter().enumerate() {
+        if platform.size[0] <= 0.0 || platform.size[1] <= 0.0 {
+            issues.push(Issue::error(
+                format!("platforms[{}]", i),
+                format!("size must be positive, found {:?}", platform.size),
+            ));
+        }
+        if platform.speed <= 0.0 {
+            issues.push(Issue::error(
+                format!("platforms[{}]", i),
+                format!("speed must be positive, found {}", platform.speed),
+            ));
+        }
+    }
+    for (s, spawner) in level.spawners.iter().enumerate() {
+        for (i, enemy) in spawner.enemies.iter().enumerate() {
+            lint_enemy(&format!("spawners[{}].enemies[{}]", s, i), enemy, issues);
+        }
+    }
+}
+
+/// blocks are rectangles centered on pos, size is half the width and height.
+/// ramps and polygons are checked by their box, so ones that only overlap in the empty corners are still reported
+fn blocks_overlap(a: &BlockData, b: &BlockData) -> bool {
+    let dx = (a.pos[0] - b.pos[0]).abs();
+    let dy = (a.pos[1] - b.pos[1]).abs();
+    dx + OVERLAP_TOLERANCE < a.size[0] + b.size[0] && dy + OVERLAP_TOLERANCE < a.size[1] + b.size[1]
+}
+
+/// enemies are circles with a radius of size
+fn enemy_in_block(enemy: &EnemyData, block: &BlockData) -> bool {
+    circle_in_block(enemy.pos, enemy.size, block)
+}
+
+fn circle_in_block(pos: [f32; 2], radius: f32, block: &BlockData) -> bool {
+    // the closest point on the block to the center of the circle
+    // abs so a negative size (reported elsewhere) does not make clamp panic
+    let (half_x, half_y) = (block.size[0].abs(), block.size[1].abs());
+    let closest_x = pos[0].clamp(block.pos[0] - half_x, block.pos[0] + half_x);
+    let closest_y = pos[1].clamp(block.pos[1] - half_y, block.pos[1] + half_y);
+    let dx = pos[0] - closest_x;
+    let dy = pos[1] - closest_y;
+    dx * dx + dy * dy + OVERLAP_TOLERANCE < radius * radius
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn errors(issues: &[Issue]) -> Vec<String> {
+        issues
+            .iter()
+            .filter(|issue| issue.severity == Severity::Error)
+            .map(|issue| format!("{}: {}", issue.location, issue.message))
+            .collect()
+    }
+
+    #[test]
+    fn the_levels_in_the_manifest_have_no_errors() {
+        let materials = read_materials().unwrap();
+        assert!(errors(&lint_materials(&materials)).is_empty());
+        for entry in read_level_manifest().unwrap() {
+            let path = Path::new(ASSET_FOLDER).join(&entry.path);
+            let found = errors(&lint_file(&path, &materials));
+            assert!(found.is_empty(), "{}: {:?}", path.display(), found);
+        }
+    }
+
+    #[test]
+    fn overlapping_blocks_are_errors() {
+        let text = format!(
+            r#"{{
+    "version": {},
+    "blocks": [
+        {{ "pos": [0.0, 500.0], "size": [50.0, 50.0] }},
+        {{ "pos": [60.0, 500.0], "size": [50.0, 50.0] }},
+        {{ "pos": [160.0, 500.0], "size": [50.0, 50.0] }}
+    ],
+    "enemies": []
+}}"#,
+            LEVEL_VERSION
+        );
+        let level = parse_level("test.json", text.as_bytes()).unwrap();
+        let found = errors(&lint_level(&level, &read_materials().unwrap()));
+        assert_eq!(found, vec![String::from("blocks[0]: overlaps blocks[1]")]);
+    }
+}
@@ -1,9 +1,20 @@
 use bevy::prelude::*;
-//use bevy_rapier2d::prelude::*;
+use bevy::render::mesh::{Indices, PrimitiveTopology};
+use bevy::render::render_asset::RenderAssetUsages;
+use bevy::sprite::Mesh2dHandle;
+use bevy_rapier2d::prelude::*;
 
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
 
+use crate::asset_loader::SceneAsset;
+use crate::burning::spawn_burn_remains;
+use crate::explosions::Explosion;
+use crate::heat::{Temperature, FLAME_TEMP};
+use crate::materials::{Material, DEFAULT_MATERIAL};
+use crate::rng::RngResource;
+use crate::sd_to_game::LevelId;
 use crate::state_system::AppState;
+use crate::water::Wet;
 pub struct BlockPlugin;
 
 #[derive(Bundle)]
@@ -15,31 +26,47 @@ pub struct BlockBundle {
 impl Plugin for BlockPlugin {
     fn build(&self, app: &mut App) {
         app
+            .add_event::<BlockBurntOut>()
+            // not just in game, the editor draws shaped blocks the same way
+            .add_systems(Update, build_shape_meshes)
             .add_systems(
                 PostUpdate, 
-                (block_burning_system).run_if(in_state(AppState::InGame))
+                (fire_spread_system, block_burning_system).chain().run_if(in_state(AppState::InGame))
             )
         ;
     }
 }
 
 /// This is an struct for information on the burn type for a block
-#[derive(Component, Debug, Clone, Copy, Deserialize)]
+#[derive(Component, Debug, Clone, Deserialize, Serialize)]
 #[allow(dead_code)]
 pub struct BlockInfo {
     /// If this can be set on fire
     pub burnable:       bool,
     /// When burnt can it be put out
     pub extinguishable: bool,
-    /// (How long it will burn, when it starts burning (preburn == f64::MAX))
-    pub burn_time:      (f32, f32),
+    /// How long it burns for from unlit to burnt out
+    pub burn_time:      f32,
+    /// if it is lit and how much of its burn_time it has left
+    #[serde(default)]
+    pub burn:           BurnState,
     // currently dont use pos and size
     // /// position of the block
     // pub pos:            Vec2,
     // /// size of the block
     // pub size:           Vec2,
-    //TODO slants, movable, explosive
-    pub texture:        BlockTexture,
+    /// the name of what it is made of in the materials file, see materials.rs
+    #[serde(alias = "texture")]
+    pub material:       String,
+    /// how long a fire has to be next to it before it catches, None uses the one for the material
+    #[serde(default)]
+    pub spread_delay:   Option<f32>,
+    /// if it is a dynamic body that can be pushed around instead of staying where it was put
+    #[serde(default)]
+    pub movable:        bool,
+    /// if it blows up when it burns out, see explosions.rs
+    #[serde(default)]
+    pub explosive:      bool,
 }
 
 impl BlockInfo {
@@ -48,48 +75,342 @@ impl BlockInfo {
         BlockInfo {
             burnable:       true,
             extinguishable: true,
-            burn_time:      (10.0, 0.0),
-            texture:        BlockTexture::Stone,
+            burn_time:      10.0,
+            burn:           BurnState::Unlit,
+            material:       DEFAULT_MATERIAL.to_string(),
+            spread_delay:   None,
+            movable:        false,
+            explosive:      false,
         }
     }
     
     #[allow(dead_code)]
-    pub fn new(burn: bool, exti: bool, btime: f32, text: BlockTexture) -> BlockInfo {
+    pub fn new(burn: bool, exti: bool, btime: f32, material: &str) -> BlockInfo {
         BlockInfo {
             burnable:       burn,
             extinguishable: exti,
-            burn_time:      (btime, 0.0),
-            texture:        text,
+            burn_time:      btime,
+            burn:           BurnState::Unlit,
+            material:       material.to_string(),
+            spread_delay:   None,
+            movable:        false,
+            explosive:      false,
         }
     }
     
-    pub fn set_burn(&mut self, start_time: f32) {
-        if self.burnable {
-            self.burn_time.1 = start_time;
+    /// lights the block if it can be, it keeps whatever it had left from being lit before.
+    /// blocks are lit by getting hot enough, see heat.rs
+    pub fn set_burn(&mut self) {
+        if self.burnable && self.burn.can_light() {
+            self.burn = BurnState::Burning { remaining: self.remaining() };
         }
     }
+
+    pub fn is_burning(&self) -> bool {
+        matches!(self.burn, BurnState::Burning { .. })
+    }
+
+    /// how long it has left to burn
+    pub fn remaining(&self) -> f32 {
+        match self.burn {
+            BurnState::Unlit => self.burn_time,
+            BurnState::Burning { remaining }
+            | BurnState::Smouldering { remaining, .. }
+            | BurnState::Extinguished { remaining } => remaining,
+            BurnState::BurntOut => 0.0,
+        }
+    }
+
+    /// how much of it has burnt, from 0.0 to 1.0
+    pub fn burn_progress(&self) -> f32 {
+        if self.burn_time <= 0.0 {
+            return if self.burn == BurnState::Unlit { 0.0 } else { 1.0 };
+        }
+        (1.0 - self.remaining() / self.burn_time).clamp(0.0, 1.0)
+    }
+
+    /// scorch putting it out, it smoulders for a bit before it is fully out
+    pub fn extinguish(&mut self) {
+        if let BurnState::Burning { remaining } = self.burn {
+            self.burn = BurnState::Smouldering { remaining, cooling: SMOULDER_TIME };
+        }
+    }
+
+    /// water putting it out, it goes straight to out
+    pub fn douse(&mut self) {
+        if let BurnState::Burning { remaining } | BurnState::Smouldering { remaining, .. } = self.burn {
+            self.burn = BurnState::Extinguished { remaining };
+        }
+    }
+
+    /// how long it takes fire to spread to this block, None if it never will.
+    /// material is the one this block is made of
+    pub fn ignite_delay(&self, material: &Material) -> Option<f32> {
+        if !self.burnable {
+            return None;
+        }
+        // stone never catches from a neighbour, even if the level says otherwise
+        material.spread_delay.map(|delay| self.spread_delay.unwrap_or(delay))
+    }
+}
+
+/// how long a block smoulders after scorch puts it out
+const SMOULDER_TIME: f32 = 2.0;
+
+/// where a block is in burning. remaining is how much of the burn_time is left,
+/// it only counts down while the block is burning so putting it out keeps the damage
+#[derive(Debug, Clone, Copy, PartialEq, Default, Deserialize, Serialize)]
+pub enum BurnState {
+    /// never been lit
+    #[default]
+    Unlit,
+    Burning { remaining: f32 },
+    /// just put out and still hot, fire next to it lights it again straight away. cooling is how long until it is out
+    Smouldering { remaining: f32, cooling: f32 },
+    /// put out, it lights like an unlit block
+    Extinguished { remaining: f32 },
+    /// done burning, it is replaced with what it leaves behind
+    BurntOut,
+}
+
+impl BurnState {
+    /// if set_burn can light it
+    pub fn can_light(&self) -> bool {
+        matches!(self, BurnState::Unlit | BurnState::Smouldering { .. } | BurnState::Extinguished { .. })
+    }
+
+    pub fn is_smouldering(&self) -> bool {
+        matches!(self, BurnState::Smouldering { .. })
+    }
+}
+
+/// which side of a ramp is the high side
+#[derive(Debug, Clone, Copy, PartialEq, Deserialize, Serialize)]
+pub enum RampSide {
+    Left,
+    Right,
+}
+
+/// the shape of a block inside its box. Points are from -1.0 to 1.0 on each axis and get scaled by the size,
+/// so moving and resizing a shaped block works the same as a normal one.
+/// A triangle is a polygon with three points, polygons have to be convex or they get turned into their hull
+#[derive(Component, Debug, Clone, PartialEq, Default, Deserialize, Serialize)]
+#[serde(tag = "type", deny_unknown_fields)]
+pub enum BlockShape {
+    /// the whole box, what every block used to be
+    #[default]
+    Rect,
+    /// a right triangle filling the bottom of the box, going up towards high
+    Ramp { high: RampSide },
+    Polygon { points: Vec<[f32; 2]> },
+}
+
+impl BlockShape {
+    pub fn is_rect(&self) -> bool {
+        *self == BlockShape::Rect
+    }
+
+    /// the corners of the shape going counter clockwise, from -1.0 to 1.0
+    pub fn points(&self) -> Vec<Vec2> {
+        match self {
+            BlockShape::Rect => vec![
+                Vec2::new(-1.0, -1.0),
+                Vec2::new(1.0, -1.0),
+                Vec2::new(1.0, 1.0),
+                Vec2::new(-1.0, 1.0),
+            ],
+            BlockShape::Ramp { high: RampSide::Right } => vec![
+                Vec2::new(-1.0, -1.0),
+                Vec2::new(1.0, -1.0),
+                Vec2::new(1.0, 1.0),
+            ],
+            BlockShape::Ramp { high: RampSide::Left } => vec![
+                Vec2::new(-1.0, -1.0),
+                Vec2::new(1.0, -1.0),
+                Vec2::new(-1.0, 1.0),
+            ],
+            BlockShape::Polygon { points } => points.iter().map(|p| Vec2::from(*p)).collect(),
+        }
+    }
+
+    /// the collider for a block, blocks are 200x200 scaled to their size so this is too
+    pub fn collider(&self) -> Collider {
+        if self.is_rect() {
+            return Collider::cuboid(100.0, 100.0);
+        }
+        let points: Vec<Vec2> = self.points().iter().map(|p| *p * 100.0).collect();
+        Collider::convex_hull(&points).unwrap_or_else(|| {
+            println!("ERROR: block shape {:?} has no area, using a rect", self);
+            Collider::cuboid(100.0, 100.0)
+        })
+    }
+
+    /// a mesh of the shape with half size half, uvs are from the box so the texture lines up with a normal block
+    fn mesh(&self, half: Vec2) -> Mesh {
+        let points = self.points();
+        let positions: Vec<[f32; 3]> = points.iter().map(|p| [p.x * half.x, p.y * half.y, 0.0]).collect();
+        let uvs: Vec<[f32; 2]> = points.iter().map(|p| [(p.x + 1.0) / 2.0, (1.0 - p.y) / 2.0]).collect();
+        let normals = vec![[0.0, 0.0, 1.0]; points.len()];
+        // convex, so a fan from the first point covers it
+        let indices: Vec<u32> = (1..points.len().saturating_sub(1) as u32)
+            .flat_map(|i| [0, i, i + 1])
+            .collect();
+        Mesh::new(PrimitiveTopology::TriangleList, RenderAssetUsages::default())
+            .with_inserted_attribute(Mesh::ATTRIBUTE_POSITION, positions)
+            .with_inserted_attribute(Mesh::ATTRIBUTE_UV_0, uvs)
+            .with_inserted_attribute(Mesh::ATTRIBUTE_NORMAL, normals)
+            .with_inserted_indices(Indices::U32(indices))
+    }
+}
+
+/// sprites can only be rects, so anything with a shape that is not gets its sprite swapped for a mesh.
+/// the mesh is the size the sprite was and keeps its texture and color
+fn build_shape_meshes(
+    mut commands: Commands,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<ColorMaterial>>,
+    query: Query<(Entity, &BlockShape, &Sprite, &Handle<Image>), Added<BlockShape>>,
+) {
+    for (entity, shape, sprite, texture) in query.iter() {
+        if shape.is_rect() {
+            continue;
+        }
+        let Some(size) = sprite.custom_size else {
+            println!("ERROR: shaped block without a size");
+            continue;
+        };
+        commands.entity(entity)
+            .remove::<Sprite>()
+            .insert((
+                Mesh2dHandle(meshes.add(shape.mesh(size / 2.0))),
+                materials.add(ColorMaterial {
+                    color: sprite.color,
+                    texture: Some(texture.clone()),
+                }),
+            ));
+    }
+}
+
+/// how far apart two blocks can be for fire to jump between them
+const SPREAD_DISTANCE: f32 = 25.0;
+
+/// on a block that is next to a fire, since is when the fire started heating it
+#[derive(Component, Debug, Clone, Copy)]
+pub struct Heating {
+    pub since: f32,
 }
 
-#[derive(Component, Debug, Clone, Copy, Deserialize)]
-pub enum BlockTexture {
-    Stone,
-    Wood,
-    Paper,
+/// sent when a block finishes burning, right before it is despawned
+#[derive(Event, Debug, Clone)]
+pub struct BlockBurntOut {
+    pub entity: Entity,
+    /// the id from the level file, if it had one
+    pub id: Option<String>,
 }
 
+/// the bottom left and top right of a block, blocks are a 200x200 sprite scaled to their size
+pub(crate) fn block_corners(trans: &Transform) -> (Vec2, Vec2) {
+    let center = trans.translation.truncate();
+    let half = trans.scale.truncate() * 100.0;
+    (center - half, center + half)
+}
+
+/// burnable blocks next to a burning block catch fire once they have been next to it for their spread delay
+fn fire_spread_system(
+    time: Res<Time>,
+    mut commands: Commands,
+    asset_server: Res<SceneAsset>,
+    mut query: Query<(Entity, &BlockInfo, &mut Temperature, &Transform, Option<&Heating>, Has<Wet>)>,
+) {
+    let current_time = time.elapsed_seconds();
+    let fires: Vec<(Vec2, Vec2)> = query
+        .iter()
+        .filter(|(_, info, _, _, _, _)| info.is_burning())
+        .map(|(_, _, _, trans, _, _)| block_corners(trans))
+        .collect();
+
+    for (entity, info, mut temp, trans, heating, wet) in query.iter_mut() {
+        let Some(mut delay) = info.ignite_delay(asset_server.materials.get(&info.material)) else {
+            continue;
+        };
+        // it is still hot from the last time
+        if info.burn.is_smouldering() {
+            delay = 0.0;
+        }
+        if !info.burn.can_light() || wet {
+            if heating.is_some() {
+                commands.entity(entity).remove::<Heating>();
+            }
+            continue;
+        }
+
+        let (min, max) = block_corners(trans);
+        let near_fire = fires.iter().any(|(f_min, f_max)| {
+            // the gap between them on each axis, negative when they overlap
+            let gap_x = (f_min.x - max.x).max(min.x - f_max.x);
+            let gap_y = (f_min.y - max.y).max(min.y - f_max.y);
+            gap_x <= SPREAD_DISTANCE && gap_y <= SPREAD_DISTANCE
+        });
+
+        match (near_fire, heating) {
+            (true, Some(heating)) => {
+                if current_time - heating.since >= delay {
+                    // it catches once it is this hot, see heat.rs
+                    temp.heat_to(FLAME_TEMP);
+                    commands.entity(entity).remove::<Heating>();
+                }
+            }
+            (true, None) => {
+                commands.entity(entity).insert(Heating { since: current_time });
+            }
+            // the fire went out or burnt away before it caught
+            (false, Some(_)) => {
+                commands.entity(entity).remove::<Heating>();
+            }
+            (false, None) => {}
+        }
+    }
+}
+
+// the burn timers count down with the game time, so they stop when it is paused and speed up and slow down with it.
+// when a block is done burning it is replaced with whatever its material leaves behind, see burning.rs.
+// explosive blocks go off instead, see explosions.rs
 fn block_burning_system (
     time: Res<Time>,
     mut commands: Commands,
-    query: Query<(Entity, &BlockInfo)>
+    asset_server: Res<SceneAsset>,
+    mut rng: ResMut<RngResource>,
+    mut query: Query<(Entity, &mut BlockInfo, &BlockShape, &Transform, Option<&LevelId>)>,
+    mut burnt_events: EventWriter<BlockBurntOut>,
+    mut explosions: EventWriter<Explosion>,
 ) {
     let current_time = time.elapsed_seconds();
-    for (entity, info) in query.iter() {
-        if info.burn_time.1 != 0.0 {
-            if current_time - info.burn_time.1 >= info.burn_time.0 {
-                //TODO for now it just despawns, later it might do more
+    let delta = time.delta_seconds();
+    for (entity, mut info, shape, trans, id) in query.iter_mut() {
+        match info.burn {
+            BurnState::Burning { remaining } if remaining > delta => {
+                info.burn = BurnState::Burning { remaining: remaining - delta };
+            }
+            BurnState::Burning { .. } => {
+                info.burn = BurnState::BurntOut;
+                spawn_burn_remains(&mut commands, &info, shape, trans, &asset_server, &mut rng, current_time);
+                if info.explosive {
+                    explosions.send(Explosion { pos: trans.translation.truncate() });
+                }
                 commands.entity(entity).despawn();
-                //println!("Burn timer started for block!");
+                burnt_events.send(BlockBurntOut {
+                    entity,
+                    id: id.map(|id| id.0.clone()),
+                });
+            }
+            BurnState::Smouldering { remaining, cooling } => {
+                info.burn = if cooling > delta {
+                    BurnState::Smouldering { remaining, cooling: cooling - delta }
+                } else {
+                    BurnState::Extinguished { remaining }
+                };
             }
+            BurnState::Unlit | BurnState::Extinguished { .. } | BurnState::BurntOut => {}
         }
     }
 }
\ No newline at end of file
@@ -0,0 +1,119 @@
+use bevy::prelude::*;
+//use bevy_rapier2d::prelude::*;
+
+// elsewhere in the project
+use crate::{
+    blocks::BlockInfo,
+    ember::EmberComponent,
+    enemies::{EnemyInfo, ProjectileType},
+    level_format::LevelData,
+    scorch::Scorch,
+    state_system::AppState,
+};
+
+// The bounds are the edges of the playfield, they come from the level file.
+// Scorch dies when they leave them, so falling off the blocks respawns them at the last checkpoint.
+// Embers, projectiles, enemies and movable blocks that leave are despawned so they do not live forever.
+// The camera is kept inside them as well, see camera::camera_control.
+
+#[derive(Bundle)]
+pub struct BoundsBundle {
+    // unused
+}
+
+pub struct BoundsPlugin;
+
+impl Plugin for BoundsPlugin {
+    fn build(&self, app: &mut App) {
+        app
+            .init_resource::<LevelBounds>()
+            .add_systems(
+                Update,
+                (scorch_out_of_bounds, despawn_out_of_bounds).run_if(in_state(AppState::InGame))
+            )
+            .add_systems(OnExit(AppState::InGame), clear_bounds)
+        ;
+    }
+}
+
+/// the bounds of the level being played, levels without bounds go on forever
+#[derive(Resource, Debug, Default, Clone, Copy)]
+pub struct LevelBounds {
+    pub rect: Option<Rect>,
+}
+
+impl LevelBounds {
+    pub fn from_level(data: &LevelData) -> Self {
+        Self { rect: data.bounds.map(|bounds| bounds.to_rect()) }
+    }
+
+    /// if pos is outside the bounds, always false when there are none
+    pub fn is_outside(&self, pos: Vec2) -> bool {
+        self.rect.is_some_and(|rect| !rect.contains(pos))
+    }
+
+    /// moves the center of a view with half size half_view so the view stays inside the bounds.
+    /// if the bounds are smaller than the view it is centered on them instead
+    pub fn clamp_view(&self, center: Vec2, half_view: Vec2) -> Vec2 {
+        let Some(rect) = self.rect else {
+            return center;
+        };
+        let clamp_axis = |c: f32, min: f32, max: f32, half: f32| {
+            if max - min < half * 2.0 {
+                (min + max) / 2.0
+            } else {
+                c.clamp(min + half, max - half)
+            }
+        };
+        Vec2::new(
+            clamp_axis(center.x, rect.min.x, rect.max.x, half_view.x),
+            clamp_axis(center.y, rect.min.y, rect.max.y, half_view.y),
+        )
+    }
+}
+
+/// leaving the bounds is the same as running out of flame
+fn scorch_out_of_bounds(
+    bounds: Res<LevelBounds>,
+    mut s_query: Query<(&mut Scorch, &Transform)>,
+) {
+    if let Ok((mut s_info, s_trans)) = s_query.get_single_mut() {
+        if !s_info.is_dead && bounds.is_outside(s_trans.translation.truncate()) {
+            println!("Scorch left the level");
+            s_info.is_dead = true;
+        }
+    }
+}
+
+/// embers, projectiles, enemies and movable blocks that leave the bounds are gone for good
+fn despawn_out_of_bounds(
+    mut commands: Commands,
+    bounds: Res<LevelBounds>,
+    query: Query<
+        (Entity, &Transform),
+        Or<(With<EmberComponent>, With<ProjectileType>, With<EnemyInfo>)>,
+    >,
+    b_query: Query<(Entity, &Transform, &BlockInfo)>,
+) {
+    if bounds.rect.is_none() {
+        return;
+    }
+    for (entity, trans) in query.iter() {
+        if bounds.is_outside(trans.translation.truncate()) {
+            commands.entity(entity).despawn_recursive();
+        }
+    }
+    // the rest of the blocks never move so they are left alone
+    for (entity, trans, info) in b_query.iter() {
+        if info.movable && bounds.is_outside(trans.translation.truncate()) {
+            commands.entity(entity).despawn_recursive();
+        }
+    }
+}
+
+/// the menus and the editor are not clamped to the last level played
+fn clear_bounds(
+    mut bounds: ResMut<LevelBounds>,
+) {
+    bounds.rect = None;
+}
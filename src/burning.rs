@@ -0,0 +1,327 @@
+use bevy::prelude::*;
+use bevy_rapier2d::prelude::*;
+use rand::Rng;
+use serde::{Deserialize, Serialize};
+
+// elsewhere in the project
+use crate::{
+    asset_loader::SceneAsset,
+    blocks::{BlockInfo, BlockShape},
+    rng::RngResource,
+    sd_to_game::{block_texture, LevelEntity},
+    state_system::AppState,
+};
+
+// How burning looks. Burning blocks get darker the further along their burn is and give off smoke and sparks,
+// the smoke and sparks are only for looks so they do not light anything or give scorch flame.
+// When a block burns out its material decides what is left, see Material::burn_out:
+// nothing, a charred remnant that can not be touched, or debris that falls and goes away after a bit.
+
+#[derive(Bundle)]
+pub struct BurnBundle {
+    // unused
+}
+
+pub struct BurnPlugin;
+
+impl Plugin for BurnPlugin {
+    fn build(&self, app: &mut App) {
+        app
+            .insert_resource(SmokeTimer(Timer::from_seconds(SMOKE_INTERVAL, TimerMode::Repeating)))
+            .add_systems(
+                Update,
+                (burn_tint_system, emit_smoke, burn_particles, debris_timeout).run_if(in_state(AppState::InGame))
+            )
+        ;
+    }
+}
+
+/// how often each burning block gives off smoke
+const SMOKE_INTERVAL: f32 = 0.25;
+/// the chance of a spark with each puff of smoke
+const SPARK_CHANCE: f64 = 0.4;
+/// the color a block is just before it burns out
+const BURNT_RGB: Vec3 = Vec3::new(0.25, 0.12, 0.05);
+/// explosive blocks are redder so they can be told apart
+const EXPLOSIVE_RGB: Vec3 = Vec3::new(1.0, 0.55, 0.45);
+/// the color charred remains are
+const CHARRED_COLOR: Color = Color::srgb(0.15, 0.15, 0.15);
+/// how long debris lasts
+const DEBRIS_LIFETIME: f32 = 3.0;
+/// half the width of a piece of debris
+const DEBRIS_SIZE: f32 = 8.0;
+
+/// what is left of a block once it burns out, set for each material in the materials file
+#[derive(Debug, Clone, Copy, PartialEq, Deserialize, Serialize)]
+pub enum BurnOut {
+    /// it is just gone
+    Vanish,
+    /// a dark copy of the block stays behind, scorch can go through it
+    Charred,
+    /// it falls apart into small pieces
+    Crumble,
+}
+
+#[derive(Resource)]
+struct SmokeTimer(Timer);
+
+/// what is left of a burnt block, size is half the width and height
+#[derive(Component, Debug, Clone)]
+pub struct Charred {
+    pub size: Vec2,
+    pub material: String,
+    pub shape: BlockShape,
+}
+
+/// a piece of a crumbled block, despawns at until
+#[derive(Component)]
+pub struct Debris {
+    pub until: f32,
+    /// what the block was made of, for the sprite
+    pub material: String,
+}
+
+/// smoke and sparks, they float up and fade
+#[derive(Component)]
+struct BurnParticle {
+    velocity: Vec2,
+    born: f32,
+    lifetime: f32,
+    alpha: f32,
+}
+
+/// blocks darken as they burn, and stay as dark as they got when put out.
+/// shaped blocks are meshes instead of sprites, they each have their own material so it is tinted instead
+fn burn_tint_system(
+    asset_server: Res<SceneAsset>,
+    mut materials: ResMut<Assets<ColorMaterial>>,
+    mut query: Query<(&BlockInfo, Option<&mut Sprite>, Option<&Handle<ColorMaterial>>)>,
+) {
+    for (info, sprite, material) in query.iter_mut() {
+        // the color of the material, redder if it is explosive
+        let [r, g, b] = asset_server.materials.get(&info.material).color;
+        let mut base = Vec3::new(r, g, b);
+        if info.explosive {
+            base *= EXPLOSIVE_RGB;
+        }
+        let rgb = base.lerp(BURNT_RGB, info.burn_progress());
+        let color = Color::srgb(rgb.x, rgb.y, rgb.z);
+        if let Some(mut sprite) = sprite {
+            if sprite.color != color {
+                sprite.color = color;
+            }
+        } else if let Some(material) = material {
+            // only get it mutably when it changes, that marks the asset as changed
+            if materials.get(material).is_some_and(|m| m.color != color) {
+                if let Some(material) = materials.get_mut(material) {
+                    material.color = color;
+                }
+            }
+        }
+    }
+}
+
+fn emit_smoke(
+    mut commands: Commands,
+    time: Res<Time>,
+    mut timer: ResMut<SmokeTimer>,
+    mut rng: ResMut<RngResource>,
+    asset_server: Res<SceneAsset>,
+    query: Query<(&BlockInfo, &Transform)>,
+) {
+    if !timer.0.tick(time.delta()).just_finished() {
+        return;
+    }
+    for (info, trans) in query.iter() {
+        // smouldering blocks still smoke, but only burning ones spark
+        if !info.is_burning() && !info.burn.is_smouldering() {
+            continue;
+        }
+        // from somewhere along the top of the block
+        let half = trans.scale.truncate() * 100.0;
+        let top = Vec2::new(
+            trans.translation.x + rng.rng.gen_range(-half.x..=half.x),
+            trans.translation.y + half.y,
+        );
+        spawn_particle(
+            &mut commands,
+            asset_server.t_temp.clone(),
+            top,
+            Vec2::new(rng.rng.gen_range(-20.0..20.0), 60.0),
+            Color::srgba(0.3, 0.3, 0.3, 0.5),
+            30.0,
+            1.5,
+            time.elapsed_seconds(),
+        );
+        if info.is_burning() && rng.rng.gen_bool(SPARK_CHANCE) {
+            spawn_particle(
+                &mut commands,
+                asset_server.t_ember.clone(),
+                top,
+                Vec2::new(rng.rng.gen_range(-60.0..60.0), rng.rng.gen_range(100.0..200.0)),
+                Color::WHITE,
+                8.0,
+                0.5,
+                time.elapsed_seconds(),
+            );
+        }
+    }
+}
+
+/// something that floats along at velocity and fades out over lifetime, it does not touch anything
+#[allow(clippy::too_many_arguments)]
+pub fn spawn_particle(
+    commands: &mut Commands,
+    texture: Handle<Image>,
+    pos: Vec2,
+    velocity: Vec2,
+    color: Color,
+    size: f32,
+    lifetime: f32,
+    born: f32,
+) {
+    commands.spawn((
+        SpriteBundle {
+            texture,
+            transform: Transform::from_xyz(pos.x, pos.y, 0.0),
+            sprite: Sprite {
+                custom_size: Some(Vec2::splat(size)),
+                color,
+                ..default()
+            },
+            ..Default::default()
+        },
+        BurnParticle { velocity, born, lifetime, alpha: color.alpha() },
+        StateScoped(AppState::InGame),
+    ));
+}
+
+fn burn_particles(
+    mut commands: Commands,
+    time: Res<Time>,
+    mut query: Query<(Entity, &BurnParticle, &mut Transform, &mut Sprite)>,
+) {
+    for (entity, particle, mut trans, mut sprite) in query.iter_mut() {
+        let age = (time.elapsed_seconds() - particle.born) / particle.lifetime;
+        if age >= 1.0 {
+            commands.entity(entity).despawn();
+            continue;
+        }
+        trans.translation += (particle.velocity * time.delta_seconds()).extend(0.0);
+        sprite.color.set_alpha(particle.alpha * (1.0 - age));
+    }
+}
+
+fn debris_timeout(
+    mut commands: Commands,
+    time: Res<Time>,
+    query: Query<(Entity, &Debris)>,
+) {
+    for (entity, debris) in query.iter() {
+        if time.elapsed_seconds() >= debris.until {
+            commands.entity(entity).despawn();
+        }
+    }
+}
+
+/// leaves behind whatever the material of a burnt out block says to, called right before the block is despawned.
+/// explosive blocks are blown apart so nothing is left of them
+pub fn spawn_burn_remains(
+    commands: &mut Commands,
+    info: &BlockInfo,
+    shape: &BlockShape,
+    trans: &Transform,
+    asset_server: &Res<SceneAsset>,
+    rng: &mut RngResource,
+    current_time: f32,
+) {
+    if info.explosive {
+        return;
+    }
+    let pos = trans.translation.truncate();
+    // blocks are a 200x200 sprite scaled to size
+    let size = trans.scale.truncate() * 100.0;
+    match asset_server.materials.get(&info.material).burn_out {
+        BurnOut::Vanish => {}
+        BurnOut::Charred => {
+            spawn_charred(commands, pos, size, &info.material, shape, asset_server);
+        }
+        BurnOut::Crumble => {
+            // about one piece for every 40x40, but not so many it lags
+            let count = ((size.x * size.y * 4.0) / 1600.0).clamp(3.0, 20.0) as usize;
+            for _ in 0..count {
+                let offset = Vec2::new(
+                    rng.rng.gen_range(-size.x..=size.x),
+                    rng.rng.gen_range(-size.y..=size.y),
+                );
+                let velocity = Vec2::new(rng.rng.gen_range(-100.0..100.0), rng.rng.gen_range(0.0..150.0));
+                spawn_debris(commands, pos + offset, velocity, &info.material, asset_server, current_time + DEBRIS_LIFETIME);
+            }
+        }
+    }
+}
+
+/// spawns a charred remnant, size is half the width and height
+pub fn spawn_charred(
+    commands: &mut Commands,
+    pos: Vec2,
+    size: Vec2,
+    material: &str,
+    shape: &BlockShape,
+    asset_server: &Res<SceneAsset>,
+) -> Entity {
+    commands
+        .spawn((
+            SpriteBundle {
+                texture: block_texture(material, asset_server),
+                // behind the blocks
+                transform: Transform::from_xyz(pos.x, pos.y, -1.5),
+                sprite: Sprite {
+                    custom_size: Some(size * 2.0),
+                    color: CHARRED_COLOR,
+                    ..default()
+                },
+                ..Default::default()
+            },
+            // same shape as the block was, see blocks::build_shape_meshes
+            shape.clone(),
+            Charred { size, material: material.to_string(), shape: shape.clone() },
+            LevelEntity,
+            StateScoped(AppState::InGame),
+        ))
+        .id()
+}
+
+/// until is when it despawns
+pub fn spawn_debris(
+    commands: &mut Commands,
+    pos: Vec2,
+    velocity: Vec2,
+    material: &str,
+    asset_server: &Res<SceneAsset>,
+    until: f32,
+) {
+    commands.spawn((
+        SpriteBundle {
+            texture: block_texture(material, asset_server),
+            transform: Transform::from_xyz(pos.x, pos.y, -1.0),
+            sprite: Sprite {
+                custom_size: Some(Vec2::splat(DEBRIS_SIZE * 2.0)),
+                color: CHARRED_COLOR,
+                ..default()
+            },
+            ..Default::default()
+        },
+        RigidBody::Dynamic,
+        Collider::cuboid(DEBRIS_SIZE, DEBRIS_SIZE),
+        CollisionGroups::new(
+            // G1 is Scorch, G2 is embers, G3 is blocks, G4 is enemies, G5 is enemy_projectiles, G6 is triggers, G7 is water, G8 is debris
+            Group::GROUP_8,
+            Group::GROUP_3,
+        ),
+        Velocity::linear(velocity),
+        Debris { until, material: material.to_string() },
+        LevelEntity,
+        StateScoped(AppState::InGame),
+    ));
+}
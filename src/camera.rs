@@ -1,7 +1,7 @@
 use bevy::prelude::*;
 //use bevy_rapier2d::prelude::*;
 
-use crate::{state_system::AppState, Scorch};
+use crate::{bounds::LevelBounds, state_system::AppState, Scorch};
 
 pub struct CameraPlugin;
 
@@ -14,7 +14,9 @@ impl Plugin for CameraPlugin {
     fn build(&self, app: &mut App) {
         // graphical and underlying stuff
         app
-            .add_systems(OnEnter(AppState::InGame), start_camera)
+            // the camera is needed for the menus as well as the game
+            .init_resource::<CameraLock>()
+            .add_systems(Startup, start_camera)
             .add_systems(
                 Update, 
                 (camera_control).run_if(in_state(AppState::InGame))
@@ -26,6 +28,12 @@ impl Plugin for CameraPlugin {
 #[derive(Component)]
 pub struct MainCamera;
 
+/// while pos is set the camera stays there instead of following scorch
+#[derive(Resource, Debug, Default)]
+pub struct CameraLock {
+    pub pos: Option<Vec2>,
+}
+
 fn start_camera(mut commands: Commands) {
     // this is the default camera
     commands.spawn((
@@ -42,18 +50,32 @@ fn start_camera(mut commands: Commands) {
 }
 
 
-// camera will follow the x axis of the Scorch
+// camera will follow the x axis of the Scorch, unless it is locked in place.
+// either way it is kept inside the level bounds
 fn camera_control(
     character_query: Query<&Transform, With<Scorch>>,
-    mut camera_query: Query<&mut Transform, (With<MainCamera>, Without<Scorch>)>,
+    mut camera_query: Query<(&mut Transform, &OrthographicProjection), (With<MainCamera>, Without<Scorch>)>,
+    lock: Res<CameraLock>,
+    bounds: Res<LevelBounds>,
 ) {
+    if let Some(pos) = lock.pos {
+        if let Ok((mut camera_transform, projection)) = camera_query.get_single_mut() {
+            let pos = bounds.clamp_view(pos, projection.area.half_size());
+            camera_transform.translation = pos.extend(camera_transform.translation.z);
+        }
+        return;
+    }
+
     // learning moment, even though there are no transforms with MainCamera and Scorch, 
     // when we are querying one to be mutable and the other immutable,
     // we need to the query of transforms with MainCamera does not contain Scorch 
     // because we cant query the same component one mutable and the other not
     if let Ok(character_transform) = character_query.get_single() {
-        if let Ok(mut camera_transform) = camera_query.get_single_mut() {
-            camera_transform.translation.x = character_transform.translation.x;
+        if let Ok((mut camera_transform, projection)) = camera_query.get_single_mut() {
+            // the editor and camera locks can move it up or down
+            let pos = Vec2::new(character_transform.translation.x, 0.0);
+            let pos = bounds.clamp_view(pos, projection.area.half_size());
+            camera_transform.translation = pos.extend(camera_transform.translation.z);
         } else {
             //println!("ERROR! camera transform unable to parse");
         }
@@ -3,7 +3,7 @@ use bevy_rapier2d::prelude::*;
 
 // elsewhere in the project
 use crate::{
-    blocks::BlockInfo, ember::EmberComponent, enemies::{EnemyInfo, ProjectileType}, scorch::{DetectRange, Scorch}, state_system::AppState
+    blocks::BlockInfo, burning::Debris, ember::EmberComponent, enemies::{EnemyInfo, ProjectileType}, heat::{Temperature, CONTACT_HEAT}, scorch::{DetectRange, Scorch}, state_system::AppState
 };
 
 #[derive(Bundle)]
@@ -38,10 +38,13 @@ fn collision_handling(
 
     mut scorch_query: Query<&mut Scorch>,
     mut ember_query: Query<&mut EmberComponent>,
-    mut block_query: Query<&mut BlockInfo>,
+    // blocks catch fire from getting hot, see heat.rs
+    mut block_query: Query<&mut Temperature, With<BlockInfo>>,
     mut enemy_query: Query<&mut EnemyInfo>,
     mut e_proj_query: Query<&mut ProjectileType>,
     detect_query: Query<&DetectRange>,
+    sensor_query: Query<(), With<Sensor>>,
+    debris_query: Query<(), With<Debris>>,
 
     cg_query: Query<&CollisionGroups>,
     tf_query: Query<&Transform>,
@@ -61,6 +64,12 @@ fn collision_handling(
                     // this orders e1 and e2 by the bits in their collision group (lower bits first)
                     //TODO if I start adding entities with more then 1 group membership see if this still works
                     let (e1, e2) = if e1_bits >= e2_bits { (*e2, *e1) } else { (*e1, *e2) };
+
+                    // triggers, switches and water are sensors with higher groups, they have their own systems.
+                    // debris is only for looks, it lands on blocks and nothing else
+                    if sensor_query.contains(e2) || debris_query.contains(e2) {
+                        continue;
+                    }
                     
                     // if e1 is scorch
                     if let Ok(mut s_info) = scorch_query.get_mut(e1) {
@@ -71,11 +80,9 @@ fn collision_handling(
                             commands.entity(e2).despawn();
                             s_info.regen_flame();
                         //scorch block collision
-                        } else if let Ok(mut b_info) = block_query.get_mut(e2) {
+                        } else if let Ok(mut b_temp) = block_query.get_mut(e2) {
                             //println!("scorch block collision");
-                            if b_info.burnable && b_info.burn_time.1 == 0.0 {
-                                b_info.set_burn(time.elapsed_seconds());
-                            }
+                            b_temp.heat(CONTACT_HEAT);
                         //scorch enemy collision
                         } else if let Ok(mut en_info) = enemy_query.get_mut(e2) {
                             //println!("scorch enemy collision");
@@ -122,11 +129,9 @@ fn collision_handling(
                     // if e1 is ember
                     } else if let Ok(mut _em_info) = ember_query.get_mut(e1) {
                         //println!("collisions are happening with ember");
-                        if let Ok(mut b_info) = block_query.get_mut(e2) {
+                        if let Ok(mut b_temp) = block_query.get_mut(e2) {
                             //println!("ember block collision");
-                            if b_info.burnable && b_info.burn_time.1 == 0.0 {
-                                b_info.set_burn(time.elapsed_seconds());
-                            }
+                            b_temp.heat(CONTACT_HEAT);
                         } else if let Ok(mut en_info) = enemy_query.get_mut(e2) {
                             //println!("ember enemy collision");
                             //TODO embers currently deal 10 dmg independent on flame level
@@ -144,7 +149,7 @@ fn collision_handling(
                             println!("ERROR: ember unknown collision {:b}, {:b}", e1_bits, e2_bits);
                         }
                     // if e1 is a block
-                    } else if let Ok(mut _b_info) = block_query.get_mut(e1) {
+                    } else if block_query.contains(e1) {
                         //println!("collisions are happening with block");
                         if let Ok(mut _en_info) = enemy_query.get_mut(e2) {
                             //println!("block enemy collision");
@@ -153,6 +158,9 @@ fn collision_handling(
                             //println!("block projectile collision");
                             //TODO for now there are only despawn on collision projectiles, later this needs to change
                             commands.entity(e2).despawn();
+                        // block block collision
+                        } else if block_query.contains(e2) {
+                            // movable blocks hitting other blocks, the physics handles it
                         } else {
                             println!("ERROR: block unknown collision {:b}, {:b}", e1_bits, e2_bits);
                         }
@@ -0,0 +1,846 @@
+use bevy::prelude::*;
+//use bevy_rapier2d::prelude::*;
+use bevy::window::PrimaryWindow;
+
+use std::path::Path;
+
+// elsewhere in the project
+use crate::{
+    asset_loader::SceneAsset,
+    blocks::{BlockShape, RampSide},
+    camera::MainCamera,
+    enemies::EnemyType,
+    level_format::{authored_level, BlockData, BlockSpec, EnemyData, EnemySpec, LevelData, SwitchData},
+    materials::MaterialRegistry,
+    pools::POOL_COLOR,
+    sd_to_game::{block_texture, CurrentLevel, LevelRegistry, Playtest},
+    state_system::AppState,
+    water::{RAIN_COLOR, WATER_COLOR},
+};
+
+#[derive(Bundle)]
+pub struct EditorBundle {
+    // unused
+}
+
+pub struct EditorPlugin;
+
+impl Plugin for EditorPlugin {
+    fn build(&self, app: &mut App) {
+        app
+            .init_resource::<EditorLevel>()
+            .init_resource::<EditorState>()
+            .add_systems(OnEnter(AppState::Editor), (enter_editor, spawn_editor_panel))
+            .add_systems(
+                Update,
+                (
+                    editor_load_level,
+                    editor_keys,
+                    editor_mouse,
+                    editor_camera_pan,
+                    sync_editor_visuals,
+                    update_editor_panel,
+                ).chain().run_if(in_state(AppState::Editor))
+            )
+            .add_systems(
+                Update,
+                (end_playtest).run_if(in_state(AppState::InGame).and_then(resource_exists::<Playtest>))
+            )
+        ;
+    }
+}
+
+/// how wide the side panel is, clicks on it do not go to the level
+const PANEL_WIDTH: f32 = 320.0;
+
+/// how fast the camera moves in the editor
+const PAN_SPEED: f32 = 1000.0;
+
+/// the level files are relative to this
+const ASSET_FOLDER: &str = "assets";
+
+/// the level being edited
+#[derive(Resource, Default)]
+pub struct EditorLevel {
+    /// the path of the level, relative to the assets folder
+    pub path: String,
+    /// the file while it is loading
+    pub handle: Handle<LevelData>,
+    /// None until the file has loaded
+    pub level: Option<LevelData>,
+}
+
+/// what the mouse does when clicking on empty space
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub enum EditorTool {
+    #[default]
+    Select,
+    PlaceBlock,
+    PlaceEnemy,
+}
+
+/// a block or enemy in the level, by index
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Selection {
+    Block(usize),
+    Enemy(usize),
+}
+
+#[derive(Resource, Default)]
+pub struct EditorState {
+    pub tool: EditorTool,
+    pub selected: Option<Selection>,
+    /// the field highlighted in the side panel
+    pub field: usize,
+    /// while dragging, the offset from the cursor to the center of what is dragged
+    pub drag_offset: Option<Vec2>,
+    /// shown at the bottom of the panel, for saves and errors
+    pub message: String,
+}
+
+/// the sprites that show the level in the editor
+#[derive(Component)]
+pub struct EditorVisual;
+
+/// the text in the side panel
+#[derive(Component)]
+pub struct EditorPanelText;
+
+/// starts loading the current level, unless it is already being edited
+fn enter_editor(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    registry: Res<LevelRegistry>,
+    current: Res<CurrentLevel>,
+    mut editor: ResMut<EditorLevel>,
+    mut state: ResMut<EditorState>,
+) {
+    // coming back from a playtest
+    commands.remove_resource::<Playtest>();
+
+    let Some(entry) = registry.get(current.index) else {
+        state.message = format!("no level at index {}", current.index);
+        return;
+    };
+    if editor.path == entry.path && editor.level.is_some() {
+        return;
+    }
+
+    editor.path = entry.path.clone();
+    editor.handle = asset_server.load(entry.path.clone());
+    editor.level = None;
+    state.selected = None;
+    state.message = format!("editing {}", entry.path);
+}
+
+/// copies the level out of the asset once it has loaded, the editor works on its own copy
+fn editor_load_level(
+    mut editor: ResMut<EditorLevel>,
+    levels: Res<Assets<LevelData>>,
+) {
+    if editor.level.is_some() {
+        return;
+    }
+    if let Some(level) = levels.get(&editor.handle) {
+        editor.level = Some(level.clone());
+    }
+}
+
+fn editor_keys(
+    mut commands: Commands,
+    key_presses: Res<ButtonInput<KeyCode>>,
+    mut editor: ResMut<EditorLevel>,
+    mut state: ResMut<EditorState>,
+    mut levels: ResMut<Assets<LevelData>>,
+    mut current: ResMut<CurrentLevel>,
+    mut next_state: ResMut<NextState<AppState>>,
+    asset_server: Res<SceneAsset>,
+) {
+    if key_presses.just_pressed(KeyCode::Escape) {
+        next_state.set(AppState::MainMenu);
+        return;
+    }
+
+    // tools
+    if key_presses.just_pressed(KeyCode::Digit1) {
+        state.tool = EditorTool::Select;
+    } else if key_presses.just_pressed(KeyCode::Digit2) {
+        state.tool = EditorTool::PlaceBlock;
+    } else if key_presses.just_pressed(KeyCode::Digit3) {
+        state.tool = EditorTool::PlaceEnemy;
+    }
+
+    // only touch the level when a key was pressed, so the visuals are not rebuilt every frame
+    if key_presses.get_just_pressed().len() == 0 {
+        return;
+    }
+    let editor = &mut *editor;
+    let Some(level) = editor.level.as_mut() else {
+        return;
+    };
+
+    let ctrl = key_presses.any_pressed([KeyCode::ControlLeft, KeyCode::ControlRight]);
+    if ctrl && key_presses.just_pressed(KeyCode::KeyS) {
+        let path = save_level(&editor.path, level);
+        state.message = path;
+        return;
+    }
+
+    // play the level as it is in the editor
+    if key_presses.just_pressed(KeyCode::KeyP) {
+        current.handle = levels.add(level.clone());
+        commands.insert_resource(Playtest);
+        next_state.set(AppState::InGame);
+        return;
+    }
+
+    let Some(selected) = state.selected else {
+        return;
+    };
+
+    if key_presses.any_just_pressed([KeyCode::Delete, KeyCode::Backspace]) {
+        match selected {
+            Selection::Block(i) => { level.blocks.remove(i); }
+            Selection::Enemy(i) => { level.enemies.remove(i); }
+        }
+        state.selected = None;
+        return;
+    }
+
+    // moving through and changing the fields in the side panel
+    let field_count = match selected {
+        Selection::Block(_) => BLOCK_FIELDS.len(),
+        Selection::Enemy(_) => ENEMY_FIELDS.len(),
+    };
+    if key_presses.just_pressed(KeyCode::ArrowDown) {
+        state.field = (state.field + 1) % field_count;
+    } else if key_presses.just_pressed(KeyCode::ArrowUp) {
+        state.field = (state.field + field_count - 1) % field_count;
+    }
+
+    let mut step = 0.0;
+    if key_presses.just_pressed(KeyCode::ArrowRight) {
+        step = 1.0;
+    } else if key_presses.just_pressed(KeyCode::ArrowLeft) {
+        step = -1.0;
+    }
+    if step != 0.0 {
+        // shift for bigger steps
+        if key_presses.any_pressed([KeyCode::ShiftLeft, KeyCode::ShiftRight]) {
+            step *= 10.0;
+        }
+        let field = state.field;
+        match selected {
+            Selection::Block(i) => edit_block_field(level, i, field, step, &asset_server.materials),
+            Selection::Enemy(i) => edit_enemy_field(&mut level.enemies[i], field, step),
+        }
+    }
+}
+
+/// left click selects and drags, or places with the place tools. right drag resizes
+fn editor_mouse(
+    mouse_input: Res<ButtonInput<MouseButton>>,
+    q_window: Query<&Window, With<PrimaryWindow>>,
+    q_camera: Query<(&Camera, &GlobalTransform), With<MainCamera>>,
+    mut editor: ResMut<EditorLevel>,
+    mut state: ResMut<EditorState>,
+) {
+    if !mouse_input.any_pressed([MouseButton::Left, MouseButton::Right])
+        && !mouse_input.just_released(MouseButton::Left)
+    {
+        return;
+    }
+    let Some(cursor) = cursor_world_pos(&q_window, &q_camera) else {
+        return;
+    };
+    let Some(level) = editor.level.as_mut() else {
+        return;
+    };
+
+    if mouse_input.just_pressed(MouseButton::Left) {
+        if let Some(picked) = pick(level, cursor) {
+            state.selected = Some(picked);
+            state.field = 0;
+            state.drag_offset = Some(selection_pos(level, picked) - cursor);
+        } else {
+            match state.tool {
+                EditorTool::Select => state.selected = None,
+                EditorTool::PlaceBlock => {
+                    level.blocks.push(BlockData {
+                        prefab: None,
+                        id: None,
+                        pos: cursor.round().into(),
+                        size: [100.0, 50.0],
+                        block_info: BlockSpec::default(),
+                        shape: BlockShape::Rect,
+                    });
+                    state.selected = Some(Selection::Block(level.blocks.len() - 1));
+                    state.field = 0;
+                }
+                EditorTool::PlaceEnemy => {
+                    level.enemies.push(EnemyData {
+                        prefab: None,
+                        pos: cursor.round().into(),
+                        size: 25.0,
+                        e_info: EnemySpec::default(),
+                    });
+                    state.selected = Some(Selection::Enemy(level.enemies.len() - 1));
+                    state.field = 0;
+                }
+            }
+        }
+    }
+
+    if mouse_input.just_released(MouseButton::Left) {
+        state.drag_offset = None;
+    }
+
+    let Some(selected) = state.selected else {
+        return;
+    };
+
+    // moving
+    if mouse_input.pressed(MouseButton::Left) {
+        if let Some(offset) = state.drag_offset {
+            let pos: [f32; 2] = (cursor + offset).round().into();
+            match selected {
+                Selection::Block(i) => level.blocks[i].pos = pos,
+                Selection::Enemy(i) => level.enemies[i].pos = pos,
+            }
+        }
+    }
+
+    // resizing, the edge follows the cursor
+    if mouse_input.pressed(MouseButton::Right) {
+        match selected {
+            Selection::Block(i) => {
+                let block = &mut level.blocks[i];
+                let half = (cursor - Vec2::from(block.pos)).abs().round().max(Vec2::splat(5.0));
+                block.size = half.into();
+            }
+            Selection::Enemy(i) => {
+                let enemy = &mut level.enemies[i];
+                enemy.size = cursor.distance(Vec2::from(enemy.pos)).round().max(5.0);
+            }
+        }
+    }
+}
+
+/// WASD moves the camera
+fn editor_camera_pan(
+    key_presses: Res<ButtonInput<KeyCode>>,
+    time: Res<Time>,
+    mut camera_query: Query<&mut Transform, With<MainCamera>>,
+) {
+    // ctrl+S is saving, not moving
+    if key_presses.any_pressed([KeyCode::ControlLeft, KeyCode::ControlRight]) {
+        return;
+    }
+    let mut dir = Vec2::ZERO;
+    if key_presses.pressed(KeyCode::KeyW) { dir.y += 1.0; }
+    if key_presses.pressed(KeyCode::KeyS) { dir.y -= 1.0; }
+    if key_presses.pressed(KeyCode::KeyA) { dir.x -= 1.0; }
+    if key_presses.pressed(KeyCode::KeyD) { dir.x += 1.0; }
+    if dir == Vec2::ZERO {
+        return;
+    }
+    if let Ok(mut camera_transform) = camera_query.get_single_mut() {
+        camera_transform.translation += (dir * PAN_SPEED * time.delta_seconds()).extend(0.0);
+    }
+}
+
+/// respawns the sprites of the level whenever the level or the selection changes
+fn sync_editor_visuals(
+    mut commands: Commands,
+    editor: Res<EditorLevel>,
+    state: Res<EditorState>,
+    asset_server: Res<SceneAsset>,
+    visual_query: Query<Entity, With<EditorVisual>>,
+) {
+    if !editor.is_changed() && !state.is_changed() {
+        return;
+    }
+    for entity in visual_query.iter() {
+        commands.entity(entity).despawn_recursive();
+    }
+    let Some(level) = &editor.level else {
+        return;
+    };
+
+    let tint = |selection: Selection| {
+        if state.selected == Some(selection) {
+            Color::srgb(1.0, 0.6, 0.6)
+        } else {
+            Color::WHITE
+        }
+    };
+
+    for (i, block) in level.blocks.iter().enumerate() {
+        commands.spawn((
+            SpriteBundle {
+                texture: block_texture(&block.block_info.material, &asset_server),
+                transform: Transform::from_xyz(block.pos[0], block.pos[1], -1.0),
+                sprite: Sprite {
+                    custom_size: Some(Vec2::from(block.size) * 2.0),
+                    color: tint(Selection::Block(i)),
+                    ..default()
+                },
+                ..Default::default()
+            },
+            // ramps and polygons are swapped to a mesh, see blocks::build_shape_meshes
+            block.shape.clone(),
+            EditorVisual,
+            StateScoped(AppState::Editor),
+        ));
+    }
+
+    for (i, enemy) in level.enemies.iter().enumerate() {
+        commands.spawn((
+            SpriteBundle {
+                texture: enemy.e_info.to_enemy_info(enemy.size).get_image(&asset_server),
+                transform: Transform::from_xyz(enemy.pos[0], enemy.pos[1], -1.0),
+                sprite: Sprite {
+                    custom_size: Some(Vec2::splat(enemy.size * 2.0)),
+                    color: tint(Selection::Enemy(i)),
+                    ..default()
+                },
+                ..Default::default()
+            },
+            EditorVisual,
+            StateScoped(AppState::Editor),
+        ));
+    }
+
+    if let Some(goal) = &level.goal {
+        commands.spawn((
+            SpriteBundle {
+                texture: asset_server.t_temp.clone(),
+                transform: Transform::from_xyz(goal.pos[0], goal.pos[1], -2.0),
+                sprite: Sprite {
+                    custom_size: Some(Vec2::from(goal.size) * 2.0),
+                    color: Color::srgba(1.0, 1.0, 1.0, 0.5),
+                    ..default()
+                },
+                ..Default::default()
+            },
+            EditorVisual,
+            StateScoped(AppState::Editor),
+        ));
+    }
+
+    for checkpoint in &level.checkpoints {
+        commands.spawn((
+            SpriteBundle {
+                texture: asset_server.t_temp.clone(),
+                transform: Transform::from_xyz(checkpoint.pos[0], checkpoint.pos[1], -2.0),
+                sprite: Sprite {
+                    custom_size: Some(Vec2::from(checkpoint.size) * 2.0),
+                    color: Color::srgba(1.0, 0.5, 0.0, 0.3),
+                    ..default()
+                },
+                ..Default::default()
+            },
+            EditorVisual,
+            StateScoped(AppState::Editor),
+        ));
+    }
+
+    for pool in &level.pools {
+        commands.spawn((
+            SpriteBundle {
+                texture: asset_server.t_temp.clone(),
+                transform: Transform::from_xyz(pool.pos[0], pool.pos[1], -2.0),
+                sprite: Sprite {
+                    custom_size: Some(Vec2::from(pool.size) * 2.0),
+                    color: POOL_COLOR,
+                    ..default()
+                },
+                ..Default::default()
+            },
+            EditorVisual,
+            StateScoped(AppState::Editor),
+        ));
+    }
+
+    let water = level.water.iter().map(|water| (water, WATER_COLOR));
+    let rain = level.rain.iter().map(|rain| (rain, RAIN_COLOR));
+    for (water, color) in water.chain(rain) {
+        commands.spawn((
+            SpriteBundle {
+                texture: asset_server.t_temp.clone(),
+                transform: Transform::from_xyz(water.pos[0], water.pos[1], -2.0),
+                sprite: Sprite {
+                    custom_size: Some(Vec2::from(water.size) * 2.0),
+                    color,
+                    ..default()
+                },
+                ..Default::default()
+            },
+            EditorVisual,
+            StateScoped(AppState::Editor),
+        ));
+    }
+
+    for trigger in &level.triggers {
+        commands.spawn((
+            SpriteBundle {
+                texture: asset_server.t_temp.clone(),
+                transform: Transform::from_xyz(trigger.pos[0], trigger.pos[1], -2.0),
+                sprite: Sprite {
+                    custom_size: Some(Vec2::from(trigger.size) * 2.0),
+                    color: Color::srgba(0.3, 0.5, 1.0, 0.3),
+                    ..default()
+                },
+                ..Default::default()
+            },
+            EditorVisual,
+            StateScoped(AppState::Editor),
+        ));
+    }
+
+    // doors and platforms look like stone, area switches are yellow
+    let mut areas: Vec<([f32; 2], [f32; 2], Color)> = Vec::new();
+    for door in &level.doors {
+        areas.push((door.pos, door.size, Color::srgb(0.6, 0.6, 0.6)));
+    }
+    for platform in &level.platforms {
+        areas.push((platform.pos, platform.size, Color::WHITE));
+        // where it moves to
+        areas.push((platform.to, platform.size, Color::srgba(1.0, 1.0, 1.0, 0.3)));
+    }
+    for switch in &level.switches {
+        if let SwitchData::Touch { pos, size, .. } | SwitchData::Ember { pos, size, .. } = switch {
+            areas.push((*pos, *size, Color::srgba(1.0, 1.0, 0.0, 0.3)));
+        }
+    }
+    for (pos, size, color) in areas {
+        commands.spawn((
+            SpriteBundle {
+                texture: asset_server.t_block_unburnable.clone(),
+                transform: Transform::from_xyz(pos[0], pos[1], -2.0),
+                sprite: Sprite {
+                    custom_size: Some(Vec2::from(size) * 2.0),
+                    color,
+                    ..default()
+                },
+                ..Default::default()
+            },
+            EditorVisual,
+            StateScoped(AppState::Editor),
+        ));
+    }
+
+    // scorch is 100 across
+    let spawn = level.spawn.unwrap_or_default();
+    commands.spawn((
+        SpriteBundle {
+            texture: asset_server.t_scorch.clone(),
+            transform: Transform::from_xyz(spawn[0], spawn[1], -2.0),
+            sprite: Sprite {
+                custom_size: Some(Vec2::splat(100.0)),
+                color: Color::srgba(1.0, 1.0, 1.0, 0.5),
+                ..default()
+            },
+            ..Default::default()
+        },
+        EditorVisual,
+        StateScoped(AppState::Editor),
+    ));
+}
+
+fn spawn_editor_panel(
+    mut commands: Commands,
+) {
+    commands
+        .spawn((
+            NodeBundle {
+                style: Style {
+                    position_type: PositionType::Absolute,
+                    right: Val::Px(0.0),
+                    top: Val::Px(0.0),
+                    width: Val::Px(PANEL_WIDTH),
+                    height: Val::Percent(100.0),
+                    padding: UiRect::all(Val::Px(10.0)),
+                    ..default()
+                },
+                background_color: Color::srgba(0.0, 0.0, 0.0, 0.7).into(),
+                ..default()
+            },
+            StateScoped(AppState::Editor),
+        ))
+        .with_children(|parent| {
+            parent.spawn((
+                TextBundle::from_section(
+                    "",
+                    TextStyle {
+                        font_size: 18.0,
+                        color: Color::WHITE,
+                        ..default()
+                    },
+                ),
+                EditorPanelText,
+            ));
+        });
+}
+
+fn update_editor_panel(
+    editor: Res<EditorLevel>,
+    state: Res<EditorState>,
+    asset_server: Res<SceneAsset>,
+    mut text_query: Query<&mut Text, With<EditorPanelText>>,
+) {
+    if !editor.is_changed() && !state.is_changed() {
+        return;
+    }
+    let Ok(mut text) = text_query.get_single_mut() else {
+        return;
+    };
+
+    let mut lines = vec![
+        format!("Level: {}", editor.path),
+        format!("Tool: {:?}", state.tool),
+        String::new(),
+    ];
+
+    match (&editor.level, state.selected) {
+        (None, _) => lines.push(String::from("loading...")),
+        (Some(_), None) => lines.push(String::from("nothing selected")),
+        (Some(level), Some(selected)) => {
+            let values = match selected {
+                Selection::Block(i) => block_field_values(&level.blocks[i], &asset_server.materials),
+                Selection::Enemy(i) => enemy_field_values(&level.enemies[i]),
+            };
+            let names: &[&str] = match selected {
+                Selection::Block(_) => &BLOCK_FIELDS,
+                Selection::Enemy(_) => &ENEMY_FIELDS,
+            };
+            lines.push(format!("{:?}", selected));
+            for (i, (name, value)) in names.iter().zip(values).enumerate() {
+                let marker = if i == state.field { ">" } else { " " };
+                lines.push(format!("{} {}: {}", marker, name, value));
+            }
+        }
+    }
+
+    lines.extend([
+        String::new(),
+        String::from("1 select, 2 place block, 3 place enemy"),
+        String::from("left drag move, right drag resize"),
+        String::from("up/down field, left/right change"),
+        String::from("(shift for x10)"),
+        String::from("delete remove, WASD move camera"),
+        String::from("P playtest, ctrl+S save, esc menu"),
+        String::new(),
+        state.message.clone(),
+    ]);
+    text.sections[0].value = lines.join("\n");
+}
+
+/// escape during a playtest goes back to the editor
+fn end_playtest(
+    key_presses: Res<ButtonInput<KeyCode>>,
+    mut next_state: ResMut<NextState<AppState>>,
+) {
+    if key_presses.just_pressed(KeyCode::Escape) {
+        next_state.set(AppState::Editor);
+    }
+}
+
+const BLOCK_FIELDS: [&str; 13] = [
+    "pos x", "pos y", "size x", "size y", "burnable", "extinguishable", "burn_time", "spread_delay", "material",
+    "shape", "movable", "explosive", "id",
+];
+
+/// the shapes the editor cycles through, polygons have to be written in the level file
+const EDITOR_SHAPES: [BlockShape; 3] = [
+    BlockShape::Rect,
+    BlockShape::Ramp { high: RampSide::Right },
+    BlockShape::Ramp { high: RampSide::Left },
+];
+
+const ENEMY_FIELDS: [&str; 10] = [
+    "pos x", "pos y", "size", "e_type", "health", "move_speed", "dmg", "range", "cooldown", "moveable",
+];
+
+/// fields the block leaves to its material show what the material has
+fn block_field_values(block: &BlockData, materials: &MaterialRegistry) -> Vec<String> {
+    let info = &block.block_info;
+    let material = materials.get(&info.material);
+    let or_material = |value: Option<String>, default: String| {
+        value.unwrap_or_else(|| format!("{} (material)", default))
+    };
+    vec![
+        block.pos[0].to_string(),
+        block.pos[1].to_string(),
+        block.size[0].to_string(),
+        block.size[1].to_string(),
+        or_material(info.burnable.map(|b| b.to_string()), material.burnable.to_string()),
+        or_material(info.extinguishable.map(|b| b.to_string()), material.extinguishable.to_string()),
+        or_material(info.burn_time.map(|t| t.to_string()), material.burn_time.to_string()),
+        or_material(
+            info.spread_delay.map(|t| t.to_string()),
+            material.spread_delay.map_or(String::from("never"), |t| t.to_string()),
+        ),
+        info.material.clone(),
+        match &block.shape {
+            BlockShape::Polygon { points } => format!("Polygon ({} points)", points.len()),
+            shape => format!("{:?}", shape),
+        },
+        info.movable.to_string(),
+        info.explosive.to_string(),
+        block.id.clone().unwrap_or_else(|| String::from("none")),
+    ]
+}
+
+fn enemy_field_values(enemy: &EnemyData) -> Vec<String> {
+    let info = &enemy.e_info;
+    vec![
+        enemy.pos[0].to_string(),
+        enemy.pos[1].to_string(),
+        enemy.size.to_string(),
+        format!("{:?}", info.e_type),
+        info.health.to_string(),
+        info.move_speed.to_string(),
+        info.dmg.to_string(),
+        info.range.to_string(),
+        info.cooldown.to_string(),
+        info.moveable.to_string(),
+    ]
+}
+
+/// changes the field of the block by step, bools flip and materials cycle.
+/// fields left to the material start from what the material has.
+/// ids can not be typed, right gives the block one that is not used yet and left takes it away
+fn edit_block_field(level: &mut LevelData, i: usize, field: usize, step: f32, materials: &MaterialRegistry) {
+    let new_id = unused_block_id(level);
+    let block = &mut level.blocks[i];
+    let info = &mut block.block_info;
+    let material = materials.get(&info.material);
+    match field {
+        0 => block.pos[0] += step * 10.0,
+        1 => block.pos[1] += step * 10.0,
+        2 => block.size[0] = (block.size[0] + step * 10.0).max(5.0),
+        3 => block.size[1] = (block.size[1] + step * 10.0).max(5.0),
+        4 => info.burnable = Some(!info.burnable.unwrap_or(material.burnable)),
+        5 => info.extinguishable = Some(!info.extinguishable.unwrap_or(material.extinguishable)),
+        6 => info.burn_time = Some((info.burn_time.unwrap_or(material.burn_time) + step * 0.5).max(0.0)),
+        7 => {
+            let delay = info.spread_delay.or(material.spread_delay).unwrap_or(0.0);
+            info.spread_delay = Some((delay + step * 0.1).max(0.0));
+        }
+        8 => {
+            let names = materials.names();
+            if !names.is_empty() {
+                let next = cycle(&names, info.material.as_str(), step).to_string();
+                info.material = next;
+            }
+        }
+        9 => {
+            // a polygon is not in the list, so it goes back to a rect
+            let index = EDITOR_SHAPES.iter().position(|s| *s == block.shape);
+            let next = match (index, step > 0.0) {
+                (None, _) => 0,
+                (Some(i), true) => (i + 1) % EDITOR_SHAPES.len(),
+                (Some(i), false) => (i + EDITOR_SHAPES.len() - 1) % EDITOR_SHAPES.len(),
+            };
+            block.shape = EDITOR_SHAPES[next].clone();
+        }
+        10 => info.movable = !info.movable,
+        11 => info.explosive = !info.explosive,
+        12 => {
+            if step > 0.0 {
+                block.id.get_or_insert(new_id);
+            } else {
+                block.id = None;
+            }
+        }
+        _ => {}
+    }
+}
+
+/// changes the field of the enemy by step, bools flip and types cycle
+fn edit_enemy_field(enemy: &mut EnemyData, field: usize, step: f32) {
+    let info = &mut enemy.e_info;
+    match field {
+        0 => enemy.pos[0] += step * 10.0,
+        1 => enemy.pos[1] += step * 10.0,
+        2 => enemy.size = (enemy.size + step).max(5.0),
+        3 => info.e_type = cycle(&EnemyType::ALL, info.e_type, step),
+        4 => info.health = (info.health + step * 10.0).max(1.0),
+        5 => info.move_speed = (info.move_speed + step).max(0.0),
+        6 => info.dmg = (info.dmg + step).max(0.0),
+        7 => info.range = (info.range + step * 10.0).max(0.0),
+        8 => info.cooldown = (info.cooldown + step * 0.1).max(0.0),
+        9 => info.moveable = !info.moveable,
+        _ => {}
+    }
+}
+
+/// block_1, block_2, ect, the first one no block in the level has
+fn unused_block_id(level: &LevelData) -> String {
+    (1..)
+        .map(|n| format!("block_{}", n))
+        .find(|id| level.blocks.iter().all(|block| block.id.as_ref() != Some(id)))
+        .unwrap_or_default()
+}
+
+/// the next (or previous for a negative step) value in the list
+fn cycle<T: Copy + PartialEq>(all: &[T], current: T, step: f32) -> T {
+    let index = all.iter().position(|v| *v == current).unwrap_or(0);
+    let next = if step > 0.0 { index + 1 } else { index + all.len() - 1 };
+    all[next % all.len()]
+}
+
+/// the block or enemy under the cursor, enemies are checked first because they are smaller
+fn pick(level: &LevelData, cursor: Vec2) -> Option<Selection> {
+    if let Some(i) = level.enemies.iter().rposition(|enemy| {
+        cursor.distance(Vec2::from(enemy.pos)) <= enemy.size
+    }) {
+        return Some(Selection::Enemy(i));
+    }
+    level.blocks.iter().rposition(|block| {
+        let offset = (cursor - Vec2::from(block.pos)).abs();
+        offset.x <= block.size[0] && offset.y <= block.size[1]
+    }).map(Selection::Block)
+}
+
+fn selection_pos(level: &LevelData, selected: Selection) -> Vec2 {
+    match selected {
+        Selection::Block(i) => Vec2::from(level.blocks[i].pos),
+        Selection::Enemy(i) => Vec2::from(level.enemies[i].pos),
+    }
+}
+
+/// the cursor in world space, None if it is off the window or over the side panel
+fn cursor_world_pos(
+    q_window: &Query<&Window, With<PrimaryWindow>>,
+    q_camera: &Query<(&Camera, &GlobalTransform), With<MainCamera>>,
+) -> Option<Vec2> {
+    let window = q_window.get_single().ok()?;
+    let (camera, camera_transform) = q_camera.get_single().ok()?;
+    let cursor = window.cursor_position()?;
+    if cursor.x > window.width() - PANEL_WIDTH {
+        return None;
+    }
+    camera
+        .viewport_to_world(camera_transform, cursor)
+        .map(|ray| ray.origin.truncate())
+}
+
+/// writes the level to its file the way it would be written by hand, returns a message saying where it went.
+/// Tiled maps are not saved, the level list loads the map so a json next to it would never be used
+fn save_level(path: &str, level: &LevelData) -> String {
+    let file_path = Path::new(ASSET_FOLDER).join(path);
+    if file_path.extension().is_some_and(|ext| ext != "json") {
+        return format!("can not save {}, Tiled maps have to be changed in Tiled", file_path.display());
+    }
+
+    let json = match authored_level(level).and_then(|level| serde_json::to_string_pretty(&level)) {
+        Ok(json) => json,
+        Err(err) => return format!("could not save: {}", err),
+    };
+    match std::fs::write(&file_path, json) {
+        Ok(()) => format!("saved to {}", file_path.display()),
+        Err(err) => format!("could not save {}: {}", file_path.display(), err),
+    }
+}
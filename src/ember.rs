@@ -104,9 +104,9 @@ pub fn spawn_ember(
         RigidBody::Dynamic,
         Collider::ball(5.0),
         CollisionGroups::new(
-            // G1 is Scorch, G2 is embers, G3 is blocks, G4 is enemies, G5 is enemy_projectiles
+            // G1 is Scorch, G2 is embers, G3 is blocks, G4 is enemies, G5 is enemy_projectiles, G6 is triggers, G7 is water, G8 is debris
             Group::GROUP_2,
-            Group::GROUP_1 | Group::GROUP_3 | Group::GROUP_4 | Group::GROUP_5,
+            Group::GROUP_1 | Group::GROUP_3 | Group::GROUP_4 | Group::GROUP_5 | Group::GROUP_6 | Group::GROUP_7,
         ),
         ActiveEvents::COLLISION_EVENTS,
         Restitution::coefficient(0.7),
@@ -114,5 +114,6 @@ pub fn spawn_ember(
             impulse: imp,
             torque_impulse: 0.0,
         },
+        StateScoped(AppState::InGame),
     ));
 }
\ No newline at end of file
@@ -0,0 +1,352 @@
+use bevy::prelude::*;
+use bevy_rapier2d::prelude::*;
+use rand::Rng;
+use rand::rngs::SmallRng;
+
+use std::collections::HashSet;
+
+// elsewhere in the project
+use crate::{
+    asset_loader::SceneAsset,
+    blocks::BlockShape,
+    bounds::LevelBounds,
+    burning::{Charred, Debris},
+    camera::MainCamera,
+    ember::EmberComponent,
+    enemies::{spawn_enemy, EnemyType},
+    level_format::{BlockData, BlockSpec, EnemyData, EnemySpec},
+    rng::RngResource,
+    scorch::{respawn_scorch, Scorch},
+    sd_to_game::{clear_level, spawn_block, LevelEntity, LevelQuery, RespawnPoint},
+    state_system::AppState,
+};
+
+// Endless mode has no level file, chunks of blocks and enemies are made up as scorch moves right.
+// Each chunk gets its own rng from the seed, so the same seed always makes the same chunks,
+// no matter what order they are made in or how much rng the embers used.
+// Set FIRE_SWIMMER_SEED to play a seed again, the seed of every run is printed when it starts.
+
+#[derive(Bundle)]
+pub struct EndlessBundle {
+    // unused
+}
+
+pub struct EndlessPlugin;
+
+impl Plugin for EndlessPlugin {
+    fn build(&self, app: &mut App) {
+        app
+            .add_systems(
+                Update,
+                (endless_reset, tag_chunk_remains, stream_chunks, endless_checkpoints, endless_input)
+                    .chain()
+                    .run_if(in_state(AppState::InGame).and_then(resource_exists::<EndlessRun>))
+            )
+            .add_systems(OnExit(AppState::InGame), end_endless_run)
+        ;
+    }
+}
+
+/// how wide a chunk is
+const CHUNK_WIDTH: f32 = 1000.0;
+/// how many chunks ahead of the camera are kept spawned
+const CHUNKS_AHEAD: i64 = 2;
+/// how many chunks behind the camera are kept spawned
+const CHUNKS_BEHIND: i64 = 1;
+/// how many chunks until the difficulty stops going up
+const MAX_DIFFICULTY_CHUNK: f32 = 30.0;
+
+/// the center of the floor blocks
+const FLOOR_Y: f32 = -100.0;
+/// half the height of the floor blocks
+const FLOOR_HALF_HEIGHT: f32 = 50.0;
+
+/// while this exists the game is in endless mode instead of playing the levels
+#[derive(Resource, Debug)]
+pub struct EndlessRun {
+    /// the chunks that are spawned right now
+    pub spawned: HashSet<i64>,
+    /// the furthest chunk scorch has reached
+    pub furthest: i64,
+}
+
+/// every entity in a chunk has this, so the chunk can be despawned when it is far enough behind
+#[derive(Component, Debug, Clone, Copy)]
+pub struct EndlessChunk(pub i64);
+
+/// starts an endless run, the seed comes from FIRE_SWIMMER_SEED or is random
+pub fn start_endless_run(
+    commands: &mut Commands,
+    rng: &mut RngResource,
+) {
+    let seed = std::env::var("FIRE_SWIMMER_SEED")
+        .ok()
+        .and_then(|seed| seed.parse().ok())
+        .unwrap_or_else(|| rng.rng.gen());
+    println!("Endless mode, seed {}", seed);
+    rng.reseed(seed);
+    commands.insert_resource(EndlessRun {
+        spawned: HashSet::new(),
+        furthest: 0,
+    });
+}
+
+fn end_endless_run(
+    mut commands: Commands,
+) {
+    commands.remove_resource::<EndlessRun>();
+}
+
+/// there is no end to the right or top, but falling into a gap kills scorch
+pub fn endless_bounds() -> LevelBounds {
+    LevelBounds {
+        rect: Some(Rect {
+            min: Vec2::new(-CHUNK_WIDTH, FLOOR_Y - 1000.0),
+            max: Vec2::splat(f32::INFINITY),
+        }),
+    }
+}
+
+/// how hard chunk index is, from 0.0 at the start to 1.0
+pub fn difficulty(index: i64) -> f32 {
+    (index as f32 / MAX_DIFFICULTY_CHUNK).clamp(0.0, 1.0)
+}
+
+/// what burnt chunk blocks leave behind goes with the chunk it was left in, or it would pile up behind scorch
+fn tag_chunk_remains(
+    mut commands: Commands,
+    query: Query<(Entity, &Transform), (Or<(Added<Charred>, Added<Debris>)>, Without<EndlessChunk>)>,
+) {
+    for (entity, trans) in query.iter() {
+        // the wall at the start sticks out left of chunk 0
+        let index = ((trans.translation.x / CHUNK_WIDTH).floor() as i64).max(0);
+        commands.entity(entity).insert(EndlessChunk(index));
+    }
+}
+
+/// spawns the chunks around the camera and despawns the ones that are too far behind
+fn stream_chunks(
+    mut commands: Commands,
+    asset_server: Res<SceneAsset>,
+    rng: Res<RngResource>,
+    mut run: ResMut<EndlessRun>,
+    camera_query: Query<&Transform, With<MainCamera>>,
+    chunk_query: Query<(Entity, &EndlessChunk)>,
+) {
+    let Ok(camera_transform) = camera_query.get_single() else {
+        return;
+    };
+    let camera_chunk = (camera_transform.translation.x / CHUNK_WIDTH).floor() as i64;
+    let first = (camera_chunk - CHUNKS_BEHIND).max(0);
+    let last = camera_chunk + CHUNKS_AHEAD;
+
+    for (entity, chunk) in chunk_query.iter() {
+        if chunk.0 < first || chunk.0 > last {
+            commands.entity(entity).despawn_recursive();
+            run.spawned.remove(&chunk.0);
+        }
+    }
+
+    for index in first..=last {
+        if run.spawned.insert(index) {
+            let mut chunk_rng = rng.stream(index as u64);
+            let (blocks, enemies) = generate_chunk(index, &mut chunk_rng);
+            spawn_chunk(&mut commands, index, &blocks, &enemies, &asset_server);
+        }
+    }
+}
+
+/// makes the blocks and enemies of one chunk, the same index and rng always make the same chunk
+pub fn generate_chunk(index: i64, rng: &mut SmallRng) -> (Vec<BlockData>, Vec<EnemyData>) {
+    let d = difficulty(index);
+    let start = index as f32 * CHUNK_WIDTH;
+    let end = start + CHUNK_WIDTH;
+    let mut blocks = Vec::new();
+    let mut enemies = Vec::new();
+
+    // a wall at the start so scorch can not fall off the left side
+    if index == 0 {
+        blocks.push(block([start - 50.0, FLOOR_Y + 300.0], [50.0, 400.0], BlockSpec::default()));
+    }
+
+    // the floor is pieces with gaps between them, the gaps get bigger and the pieces smaller
+    let mut x = start;
+    let mut first_piece = true;
+    while x < end {
+        // the first piece always starts at the start of the chunk, so there is somewhere to respawn
+        if !first_piece && rng.gen_bool((0.2 + 0.5 * d) as f64) {
+            x += rng.gen_range(100.0..(150.0 + 250.0 * d));
+        }
+        let length = rng.gen_range((300.0 - 100.0 * d)..(700.0 - 300.0 * d)).min(end - x);
+        if length < 50.0 {
+            break;
+        }
+        let center = x + length / 2.0;
+        blocks.push(block([center, FLOOR_Y], [length / 2.0, FLOOR_HALF_HEIGHT], BlockSpec::default()));
+
+        // a wall in the way, the further it goes the less of them can be burned through
+        if !first_piece && length > 200.0 && rng.gen_bool(0.5) {
+            let burnable = rng.gen_bool((0.8 - 0.5 * d) as f64);
+            let spec = if burnable {
+                let paper = rng.gen_bool(0.5);
+                BlockSpec {
+                    material: String::from(if paper { "Paper" } else { "Wood" }),
+                    burn_time: Some(if paper { 0.5 } else { 3.0 }),
+                    ..default()
+                }
+            } else {
+                BlockSpec::default()
+            };
+            // stone walls are short enough to jump
+            let half_height = if burnable { 200.0 } else { rng.gen_range(50.0..100.0) };
+            let wall_x = rng.gen_range((x + 50.0)..(x + length - 50.0));
+            let bottom = FLOOR_Y + FLOOR_HALF_HEIGHT;
+            blocks.push(block([wall_x, bottom + half_height], [25.0, half_height], spec));
+        }
+
+        // enemies over the floor, more of them and meaner ones later on
+        if !first_piece || index > 0 {
+            let count = rng.gen_range(0..=(1 + (3.0 * d) as usize));
+            for _ in 0..count {
+                let e_type = pick_enemy_type(d, rng);
+                let size = 25.0;
+                enemies.push(EnemyData {
+                    prefab: None,
+                    pos: [rng.gen_range(x..(x + length)), FLOOR_Y + 200.0 + rng.gen_range(0.0..200.0)],
+                    size,
+                    e_info: EnemySpec {
+                        e_type,
+                        health: 100.0 + 100.0 * d,
+                        move_speed: 10.0 + 10.0 * d,
+                        moveable: !matches!(e_type, EnemyType::Stationary | EnemyType::StationaryRanged),
+                        ..default()
+                    },
+                });
+            }
+        }
+
+        x += length;
+        first_piece = false;
+    }
+
+    (blocks, enemies)
+}
+
+fn block(pos: [f32; 2], size: [f32; 2], block_info: BlockSpec) -> BlockData {
+    BlockData {
+        prefab: None,
+        id: None,
+        pos,
+        size,
+        block_info,
+        shape: BlockShape::Rect,
+    }
+}
+
+/// the easy enemies get less likely and the hard ones more likely as d goes up
+fn pick_enemy_type(d: f32, rng: &mut SmallRng) -> EnemyType {
+    let weights = [
+        (EnemyType::RunDown, 1.0),
+        (EnemyType::Stationary, 1.0 - 0.5 * d),
+        (EnemyType::Ranged, 0.2 + 0.8 * d),
+        (EnemyType::StationaryRanged, 0.1 + 0.6 * d),
+        (EnemyType::Summoner, 0.5 * d),
+    ];
+    let total: f32 = weights.iter().map(|(_, w)| w).sum();
+    let mut roll = rng.gen_range(0.0..total);
+    for (e_type, weight) in weights {
+        if roll < weight {
+            return e_type;
+        }
+        roll -= weight;
+    }
+    EnemyType::RunDown
+}
+
+fn spawn_chunk(
+    commands: &mut Commands,
+    index: i64,
+    blocks: &[BlockData],
+    enemies: &[EnemyData],
+    asset_server: &Res<SceneAsset>,
+) {
+    for block in blocks {
+        let b_ent = spawn_block(
+            commands,
+            Vec2::from(block.pos),
+            Vec2::from(block.size),
+            block.block_info.to_block_info(&asset_server.materials),
+            &block.shape,
+            asset_server,
+        );
+        commands.entity(b_ent).insert(EndlessChunk(index));
+    }
+    for enemy in enemies {
+        let e_ent = spawn_enemy(
+            commands,
+            Vec2::from(enemy.pos),
+            enemy.e_info.to_enemy_info(enemy.size),
+            enemy.size,
+            asset_server,
+        );
+        commands.entity(e_ent).insert((EndlessChunk(index), LevelEntity));
+    }
+}
+
+/// the start of every chunk scorch reaches is where they respawn
+fn endless_checkpoints(
+    mut run: ResMut<EndlessRun>,
+    mut respawn: ResMut<RespawnPoint>,
+    s_query: Query<&Transform, With<Scorch>>,
+) {
+    let Ok(s_trans) = s_query.get_single() else {
+        return;
+    };
+    let chunk = (s_trans.translation.x / CHUNK_WIDTH).floor() as i64;
+    if chunk > run.furthest {
+        run.furthest = chunk;
+        respawn.pos = Vec2::new(chunk as f32 * CHUNK_WIDTH + 100.0, FLOOR_Y + FLOOR_HALF_HEIGHT + 60.0);
+        println!("Endless mode: reached chunk {}", chunk);
+    }
+}
+
+/// shift+R starts the run over from the first chunk with the same seed
+fn endless_reset(
+    mut commands: Commands,
+    key_presses: Res<ButtonInput<KeyCode>>,
+    mut run: ResMut<EndlessRun>,
+    mut respawn: ResMut<RespawnPoint>,
+    level_query: LevelQuery,
+    ember_query: Query<Entity, With<EmberComponent>>,
+    mut s_query: Query<(&mut Scorch, &mut ExternalImpulse, &mut Velocity, &mut Transform)>,
+) {
+    if
+        !key_presses.just_pressed(KeyCode::KeyR)
+        || !key_presses.any_pressed([KeyCode::ShiftLeft, KeyCode::ShiftRight])
+    {
+        return;
+    }
+    println!("Restarting endless run");
+    // every chunk block and enemy is a level entity
+    clear_level(&mut commands, &level_query);
+    for entity in ember_query.iter() {
+        commands.entity(entity).despawn();
+    }
+    run.spawned.clear();
+    run.furthest = 0;
+    respawn.pos = Vec2::ZERO;
+    if let Ok((mut s_info, mut s_impulse, mut s_velocity, mut s_position)) = s_query.get_single_mut() {
+        *s_info = Scorch::default();
+        respawn_scorch(&mut s_info, &mut s_impulse, &mut s_velocity, &mut s_position, respawn.pos);
+    }
+}
+
+/// Escape ends the run
+fn endless_input(
+    key_presses: Res<ButtonInput<KeyCode>>,
+    mut next_state: ResMut<NextState<AppState>>,
+) {
+    if key_presses.just_pressed(KeyCode::Escape) {
+        next_state.set(AppState::MainMenu);
+    }
+}
@@ -1,9 +1,9 @@
 use bevy::prelude::*;
 use bevy_rapier2d::prelude::*;
 
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
 
-use crate::{asset_loader::SceneAsset, scorch::Scorch, state_system::AppState};
+use crate::{asset_loader::SceneAsset, heat::Temperature, scorch::Scorch, state_system::AppState};
 
 pub struct EnemyPlugin;
 
@@ -28,7 +28,7 @@ impl Plugin for EnemyPlugin {
 }
 
 /// Information on enemies
-#[derive(Component, Debug, Clone, Copy, Deserialize)]
+#[derive(Component, Debug, Clone, Copy, Deserialize, Serialize)]
 #[allow(dead_code)]
 pub struct EnemyInfo {
     pub e_type: EnemyType,
@@ -157,7 +157,7 @@ impl EnemyInfo {
 }
 
 /// the type of enemy
-#[derive(Debug, Clone, Copy, Deserialize, PartialEq, Default)]
+#[derive(Debug, Clone, Copy, Deserialize, Serialize, PartialEq, Default)]
 #[allow(dead_code, unreachable_patterns)]
 pub enum EnemyType {
     #[default]
@@ -168,8 +168,19 @@ pub enum EnemyType {
     Summoner,
 }
 
+impl EnemyType {
+    /// every enemy type, in the order the editor cycles through them
+    pub const ALL: [EnemyType; 5] = [
+        EnemyType::RunDown,
+        EnemyType::Ranged,
+        EnemyType::Stationary,
+        EnemyType::StationaryRanged,
+        EnemyType::Summoner,
+    ];
+}
+
 /// enum to store all the projectile structs
-#[derive(Component, Debug, Clone, Copy, Deserialize, PartialEq)]
+#[derive(Component, Debug, Clone, Copy, Deserialize, Serialize, PartialEq)]
 #[allow(dead_code)]
 pub enum ProjectileType {
     Contact(ContactProj),
@@ -223,7 +234,7 @@ impl ProjectileType {
 
 /// this type of projectile 
 /// moves a specific speed, (spd) at size (size) and on contact does (dmg) damage
-#[derive(Debug, Clone, Copy, Deserialize, PartialEq)]
+#[derive(Debug, Clone, Copy, Deserialize, Serialize, PartialEq)]
 #[allow(dead_code)]
 pub struct ContactProj {
     dmg: f32,
@@ -299,7 +310,7 @@ fn enemy_movement_system(
                         scorch_pos.distance(e_trans.translation.truncate()),
                         false,
                         QueryFilter::default().exclude_sensors().groups(CollisionGroups::new(
-                            // G1 is Scorch, G2 is embers, G3 is blocks, G4 is enemies, G5 is enemy_projectiles
+                            // G1 is Scorch, G2 is embers, G3 is blocks, G4 is enemies, G5 is enemy_projectiles, G6 is triggers, G7 is water, G8 is debris
                             Group::GROUP_4,
                             Group::GROUP_1 | Group::GROUP_3,
                         )),
@@ -361,14 +372,14 @@ fn enemy_movement_system(
 }
 
 #[allow(dead_code, unreachable_patterns)]
-/// spawns an enemy on the location specified, with the info specified,
+/// spawns an enemy on the location specified, with the info specified, and returns the entity
 pub fn spawn_enemy(
     commands: &mut Commands,
     e_pos: Vec2,
     e_info: EnemyInfo,
     e_size: f32,
     asset_server: &Res<SceneAsset>,
-) {
+) -> Entity {
     commands
         .spawn((
             SpriteBundle {
@@ -387,9 +398,9 @@ pub fn spawn_enemy(
 
             // default settings
             CollisionGroups::new(
-                // G1 is Scorch, G2 is embers, G3 is blocks, G4 is enemies, G5 is enemy_projectiles
+                // G1 is Scorch, G2 is embers, G3 is blocks, G4 is enemies, G5 is enemy_projectiles, G6 is triggers, G7 is water, G8 is debris
                 Group::GROUP_4,
-                Group::GROUP_1 | Group::GROUP_2 | Group::GROUP_3,
+                Group::GROUP_1 | Group::GROUP_2 | Group::GROUP_3 | Group::GROUP_7,
             ),
             RigidBody::Dynamic,
             Restitution::coefficient(0.5),
@@ -399,11 +410,14 @@ pub fn spawn_enemy(
             ColliderMassProperties::Density(1.0),
             LockedAxes::ROTATION_LOCKED,
             ActiveEvents::COLLISION_EVENTS,
-        ));
+            Temperature::default(),
+            StateScoped(AppState::InGame),
+        ))
+        .id()
 }
 
 #[allow(dead_code, unreachable_patterns)]
-/// spawns an projectile 
+/// spawns an projectile and returns the entity
 pub fn ranged_enemy_shoot(
     commands: &mut Commands,
     p_pos: Vec2,
@@ -411,7 +425,7 @@ pub fn ranged_enemy_shoot(
     p_type: ProjectileType,
     //e_type: EnemyInfo,
     asset_server: &Res<SceneAsset>,
-) {
+) -> Entity {
     //println!("shoot");
     commands
         .spawn((
@@ -428,7 +442,7 @@ pub fn ranged_enemy_shoot(
             //TransformBundle::from(Transform::from_xyz(p_pos.x, p_pos.y, 0.0)),
             Collider::ball(p_type.get_size()),
             CollisionGroups::new(
-                // G1 is Scorch, G2 is embers, G3 is blocks, G4 is enemies, G5 is enemy_projectiles
+                // G1 is Scorch, G2 is embers, G3 is blocks, G4 is enemies, G5 is enemy_projectiles, G6 is triggers, G7 is water, G8 is debris
                 Group::GROUP_5,
                 //TODO currently I just want the projectiles interacting with blocks and scorch
                 Group::GROUP_1 | Group::GROUP_3,
@@ -445,5 +459,7 @@ pub fn ranged_enemy_shoot(
             Velocity::default(),
             GravityScale(0.0),
             ActiveEvents::COLLISION_EVENTS,
-        ));
-}
\ No newline at end of file
+            StateScoped(AppState::InGame),
+        ))
+        .id()
+}
@@ -0,0 +1,133 @@
+use bevy::prelude::*;
+use bevy_rapier2d::prelude::*;
+use rand::Rng;
+
+// elsewhere in the project
+use crate::{
+    asset_loader::SceneAsset,
+    blocks::{block_corners, BlockInfo},
+    burning::spawn_particle,
+    ember::spawn_ember,
+    enemies::EnemyInfo,
+    heat::{Temperature, FLAME_TEMP},
+    rng::RngResource,
+    scorch::Scorch,
+    state_system::AppState,
+};
+
+// Explosive blocks (powder kegs) go off when their burn runs out, see blocks::block_burning_system.
+// Everything in the blast radius gets pushed away, scorch and enemies get hurt and burnable blocks catch fire,
+// all of it less the further from the center it is. Embers spray out too so the fire keeps going past the radius.
+// A keg that catches from another keg goes off once it has burned, so they can be chained.
+
+#[derive(Bundle)]
+pub struct ExplosionBundle {
+    // unused
+}
+
+pub struct ExplosionPlugin;
+
+impl Plugin for ExplosionPlugin {
+    fn build(&self, app: &mut App) {
+        app
+            .add_event::<Explosion>()
+            .add_systems(
+                Update,
+                (explosion_system).run_if(in_state(AppState::InGame))
+            )
+        ;
+    }
+}
+
+// TODO change the physics of the entier game to mean this does not need to be 100k
+/// a modifier added to impulses, the same as the one scorch uses
+const FORCE_STRENGTH: f32 = 99999.9;
+
+/// how far an explosion reaches
+const BLAST_RADIUS: f32 = 300.0;
+/// the impulse at the center, scorch jumping is 30
+const BLAST_IMPULSE: f32 = 60.0;
+/// the damage at the center, to scorch and enemies
+const BLAST_DMG: f32 = 40.0;
+/// how many embers spray out
+const BLAST_EMBERS: usize = 12;
+/// the color of the flash
+const FLASH_COLOR: Color = Color::srgba(1.0, 0.6, 0.1, 0.8);
+
+/// sent when an explosive block burns out, the block is already gone
+#[derive(Event, Debug, Clone, Copy)]
+pub struct Explosion {
+    pub pos: Vec2,
+}
+
+/// 1.0 at the center down to 0.0 at the edge of the blast
+fn falloff(center: Vec2, pos: Vec2) -> f32 {
+    (1.0 - center.distance(pos) / BLAST_RADIUS).max(0.0)
+}
+
+fn explosion_system(
+    mut commands: Commands,
+    mut explosions: EventReader<Explosion>,
+    time: Res<Time>,
+    mut rng: ResMut<RngResource>,
+    asset_server: Res<SceneAsset>,
+    mut s_query: Query<(&mut Scorch, &Transform)>,
+    mut e_query: Query<(Entity, &mut EnemyInfo, &Transform)>,
+    mut b_query: Query<(&mut Temperature, &Transform), With<BlockInfo>>,
+    mut imp_query: Query<(&Transform, &mut ExternalImpulse)>,
+) {
+    let current_time = time.elapsed_seconds();
+    for explosion in explosions.read() {
+        let center = explosion.pos;
+
+        // anything that can be pushed, fixed blocks ignore it
+        for (trans, mut imp) in imp_query.iter_mut() {
+            let pos = trans.translation.truncate();
+            let strength = falloff(center, pos);
+            if strength > 0.0 {
+                let dir = (pos - center).normalize_or(Vec2::Y);
+                imp.impulse += dir * BLAST_IMPULSE * strength * FORCE_STRENGTH;
+            }
+        }
+
+        if let Ok((mut s_info, s_trans)) = s_query.get_single_mut() {
+            let strength = falloff(center, s_trans.translation.truncate());
+            if strength > 0.0 {
+                s_info.damage_flame(BLAST_DMG * strength, current_time);
+            }
+        }
+
+        for (entity, mut en_info, trans) in e_query.iter_mut() {
+            let strength = falloff(center, trans.translation.truncate());
+            if strength > 0.0 && en_info.take_dmg(BLAST_DMG * strength) {
+                //this happens when the enemy is dead
+                en_info.death_effect();
+                commands.entity(entity).despawn();
+            }
+        }
+
+        // blocks catch if any part of them is in the radius, not just the center. wet ones do not
+        for (mut temp, trans) in b_query.iter_mut() {
+            let (min, max) = block_corners(trans);
+            let closest = center.clamp(min, max);
+            if closest.distance(center) <= BLAST_RADIUS {
+                temp.heat_to(FLAME_TEMP);
+            }
+        }
+
+        for _ in 0..BLAST_EMBERS {
+            let dir = Vec2::from_angle(rng.rng.gen_range(0.0..std::f32::consts::TAU));
+            spawn_ember(&mut commands, &asset_server, center + dir * 20.0, dir * FORCE_STRENGTH);
+        }
+        spawn_particle(
+            &mut commands,
+            asset_server.t_ember.clone(),
+            center,
+            Vec2::ZERO,
+            FLASH_COLOR,
+            BLAST_RADIUS * 2.0,
+            0.3,
+            current_time,
+        );
+    }
+}
@@ -0,0 +1,247 @@
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+
+// elsewhere in the project
+use crate::{
+    asset_loader::SceneAsset,
+    blocks::{block_corners, BlockInfo},
+    ember::EmberComponent,
+    enemies::EnemyInfo,
+    scorch::Scorch,
+    state_system::AppState,
+    water::Wet,
+};
+
+// Blocks, enemies and scorch all have a temperature. Scorch, embers and burning blocks give off heat that
+// falls off with distance, everything else drifts towards the heat around it.
+// A block catches fire once it reaches the ignition temperature of its material (see materials.rs),
+// so paper can catch just from scorch being close and stone never does. Touching scorch or an ember heats a block
+// all at once (see coll.rs), which is enough for most materials.
+// Wet things are cooled down to the water and can not catch until they dry off, see water.rs.
+// Fire spreading between blocks is still the spread delay (see blocks::fire_spread_system),
+// so burning blocks only heat scorch and enemies, enemies that get too hot take damage.
+
+#[derive(Bundle)]
+pub struct HeatBundle {
+    // unused
+}
+
+pub struct HeatPlugin;
+
+impl Plugin for HeatPlugin {
+    fn build(&self, app: &mut App) {
+        app
+            .add_systems(
+                Update,
+                (radiate_heat, ignite_hot_blocks, heat_damage).chain().run_if(in_state(AppState::InGame))
+            )
+        ;
+    }
+}
+
+/// what everything starts at and cools back down to
+pub const AMBIENT_TEMP: f32 = 20.0;
+/// what scorch is on its own
+pub const SCORCH_TEMP: f32 = 500.0;
+/// what burning blocks are, everything that catches from being next to a fire is heated to it
+pub const FLAME_TEMP: f32 = 800.0;
+/// how much touching scorch or an ember heats a block, it is added on top of what the block is
+pub const CONTACT_HEAT: f32 = 250.0;
+/// what wet things get cooled to
+const WATER_TEMP: f32 = 5.0;
+/// what embers give off
+const EMBER_TEMP: f32 = 400.0;
+
+/// how far the heat from scorch reaches from its center
+const SCORCH_HEAT_RADIUS: f32 = 150.0;
+/// how far the heat from an ember reaches
+const EMBER_HEAT_RADIUS: f32 = 60.0;
+/// how far the heat from a burning block reaches from its edges
+const FIRE_HEAT_RADIUS: f32 = 100.0;
+
+/// how fast things heat up and cool down, the part of the difference that goes each second
+const HEAT_RATE: f32 = 2.0;
+/// how fast wet things cool down
+const WET_COOL_RATE: f32 = 5.0;
+
+/// above this enemies start to burn
+const ENEMY_BURN_TEMP: f32 = 150.0;
+/// damage per second for each degree an enemy is over ENEMY_BURN_TEMP
+const HEAT_DMG: f32 = 0.05;
+
+/// how hot something is
+#[derive(Component, Debug, Clone, Copy, PartialEq, Deserialize, Serialize)]
+pub struct Temperature {
+    pub degrees: f32,
+}
+
+impl Default for Temperature {
+    fn default() -> Self {
+        Self { degrees: AMBIENT_TEMP }
+    }
+}
+
+impl Temperature {
+    pub fn new(degrees: f32) -> Self {
+        Self { degrees }
+    }
+
+    /// adds degrees all at once
+    pub fn heat(&mut self, degrees: f32) {
+        self.degrees += degrees;
+    }
+
+    /// heats it up to degrees if it is not that hot already
+    pub fn heat_to(&mut self, degrees: f32) {
+        self.degrees = self.degrees.max(degrees);
+    }
+
+    /// cools it down to degrees if it is hotter than that
+    pub fn cool_to(&mut self, degrees: f32) {
+        self.degrees = self.degrees.min(degrees);
+    }
+
+    /// moves it towards target, rate is the part of the difference that goes each second
+    fn approach(&mut self, target: f32, rate: f32, delta: f32) {
+        self.degrees += (target - self.degrees) * (rate * delta).min(1.0);
+    }
+}
+
+/// something giving off heat
+struct HeatSource {
+    entity: Entity,
+    /// a point, or the bottom left and top right of a burning block
+    min: Vec2,
+    max: Vec2,
+    degrees: f32,
+    radius: f32,
+}
+
+impl HeatSource {
+    /// the heat over ambient this gives to something with the bottom left and top right of r_min and r_max,
+    /// falling off from all of it at the source to none at the radius
+    fn heat_at(&self, r_min: Vec2, r_max: Vec2) -> f32 {
+        // the gap between them on each axis, 0.0 when they overlap
+        let gap = (self.min - r_max).max(r_min - self.max).max(Vec2::ZERO);
+        let falloff = (1.0 - gap.length() / self.radius).max(0.0);
+        (self.degrees - AMBIENT_TEMP).max(0.0) * falloff
+    }
+}
+
+/// everything drifts towards the heat around it, scorch and burning blocks do not go below their own heat
+fn radiate_heat(
+    time: Res<Time>,
+    ember_query: Query<&Transform, With<EmberComponent>>,
+    mut s_query: Query<(Entity, &Transform, &mut Temperature, Has<Wet>), With<Scorch>>,
+    mut b_query: Query<(&BlockInfo, &Transform, &mut Temperature, Has<Wet>), Without<Scorch>>,
+    mut e_query: Query<(Entity, &Transform, &mut Temperature, Has<Wet>), (With<EnemyInfo>, Without<Scorch>, Without<BlockInfo>)>,
+) {
+    let delta = time.delta_seconds();
+
+    let mut sources: Vec<HeatSource> = ember_query
+        .iter()
+        .map(|trans| HeatSource {
+            entity: Entity::PLACEHOLDER,
+            min: trans.translation.truncate(),
+            max: trans.translation.truncate(),
+            degrees: EMBER_TEMP,
+            radius: EMBER_HEAT_RADIUS,
+        })
+        .collect();
+    for (entity, trans, temp, _) in s_query.iter() {
+        sources.push(HeatSource {
+            entity,
+            min: trans.translation.truncate(),
+            max: trans.translation.truncate(),
+            degrees: temp.degrees,
+            radius: SCORCH_HEAT_RADIUS,
+        });
+    }
+    // these only heat scorch and enemies
+    let fires: Vec<HeatSource> = b_query
+        .iter()
+        .filter(|(info, _, _, _)| info.is_burning())
+        .map(|(_, trans, temp, _)| {
+            let (min, max) = block_corners(trans);
+            HeatSource { entity: Entity::PLACEHOLDER, min, max, degrees: temp.degrees, radius: FIRE_HEAT_RADIUS }
+        })
+        .collect();
+
+    // the temperature of the air around something, sources never heat themselves
+    let around = |entity: Entity, min: Vec2, max: Vec2, sources: &[&HeatSource]| {
+        let heat: f32 = sources
+            .iter()
+            .filter(|source| source.entity != entity)
+            .map(|source| source.heat_at(min, max))
+            .sum();
+        (AMBIENT_TEMP + heat).min(FLAME_TEMP)
+    };
+    let all: Vec<&HeatSource> = sources.iter().chain(fires.iter()).collect();
+    let no_fires: Vec<&HeatSource> = sources.iter().collect();
+
+    for (entity, trans, mut temp, wet) in s_query.iter_mut() {
+        if wet {
+            temp.approach(WATER_TEMP, WET_COOL_RATE, delta);
+            continue;
+        }
+        let pos = trans.translation.truncate();
+        let target = around(entity, pos, pos, &all).max(SCORCH_TEMP);
+        temp.approach(target, HEAT_RATE, delta);
+    }
+
+    for (entity, trans, mut temp, wet) in e_query.iter_mut() {
+        if wet {
+            temp.approach(WATER_TEMP, WET_COOL_RATE, delta);
+            continue;
+        }
+        let pos = trans.translation.truncate();
+        temp.approach(around(entity, pos, pos, &all), HEAT_RATE, delta);
+    }
+
+    for (info, trans, mut temp, wet) in b_query.iter_mut() {
+        if wet {
+            temp.approach(WATER_TEMP, WET_COOL_RATE, delta);
+            continue;
+        }
+        let (min, max) = block_corners(trans);
+        let mut target = around(Entity::PLACEHOLDER, min, max, &no_fires);
+        if info.is_burning() {
+            target = target.max(FLAME_TEMP);
+        }
+        temp.approach(target, HEAT_RATE, delta);
+    }
+}
+
+/// blocks that reach the ignition temperature of their material catch fire, wet ones have to dry off first
+fn ignite_hot_blocks(
+    asset_server: Res<SceneAsset>,
+    mut query: Query<(&mut BlockInfo, &Temperature), Without<Wet>>,
+) {
+    for (mut info, temp) in query.iter_mut() {
+        if !info.burnable || !info.burn.can_light() {
+            continue;
+        }
+        if temp.degrees >= asset_server.materials.get(&info.material).ignition_temp {
+            info.set_burn();
+        }
+    }
+}
+
+/// enemies that are too hot burn
+fn heat_damage(
+    time: Res<Time>,
+    mut commands: Commands,
+    mut query: Query<(Entity, &mut EnemyInfo, &Temperature)>,
+) {
+    for (entity, mut en_info, temp) in query.iter_mut() {
+        if temp.degrees <= ENEMY_BURN_TEMP {
+            continue;
+        }
+        let dmg = (temp.degrees - ENEMY_BURN_TEMP) * HEAT_DMG * time.delta_seconds();
+        if en_info.take_dmg(dmg) {
+            //this happens when the enemy is dead
+            en_info.death_effect();
+            commands.entity(entity).despawn();
+        }
+    }
+}
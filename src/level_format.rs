@@ -0,0 +1,1219 @@
+use bevy::prelude::*;
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+use serde_path_to_error::Segment;
+
+// elsewhere in the project
+use crate::blocks::{BlockInfo, BlockShape};
+use crate::enemies::{EnemyInfo, EnemyType};
+use crate::materials::{MaterialRegistry, DEFAULT_MATERIAL};
+
+// The level files are written by hand so they have their own format, separate from the components.
+// This way the components can change without breaking every level, the spec structs here get
+// turned into the components when the level is spawned.
+// When the format changes, bump LEVEL_VERSION and add a migration so older files still load.
+
+/// the version of the level format written by this version of the game
+pub const LEVEL_VERSION: u32 = 6;
+
+/// each migration takes a level from version (index + 1) to version (index + 2)
+const MIGRATIONS: [fn(&mut Value); 5] = [
+    migrate_v1_to_v2,
+    migrate_v2_to_v3,
+    migrate_v3_to_v4,
+    migrate_v4_to_v5,
+    migrate_v5_to_v6,
+];
+
+/// a level file, loaded through the AssetServer by the LevelLoader
+#[derive(Asset, TypePath, Clone, Deserialize, Serialize)]
+#[serde(deny_unknown_fields)]
+pub struct LevelData {
+    /// files without a version are version 1
+    pub version: u32,
+    /// named blocks and enemies that the entries can use, they are already applied to the entries
+    #[serde(default)]
+    pub prefabs: Prefabs,
+    pub blocks: Vec<BlockData>,
+    pub enemies: Vec<EnemyData>,
+    #[serde(default)]
+    pub goal: Option<GoalData>,
+    /// where scorch starts, the origin if it is not set
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub spawn: Option<[f32; 2]>,
+    /// touching one of these makes scorch respawn there instead of at the spawn
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub checkpoints: Vec<CheckpointData>,
+    /// areas that do something when scorch enters them
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub triggers: Vec<TriggerData>,
+    /// switches, logic and the doors, platforms and spawners wired to them
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub switches: Vec<SwitchData>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub logic: Vec<LogicData>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub doors: Vec<DoorData>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub platforms: Vec<PlatformData>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub spawners: Vec<SpawnerData>,
+    /// leaving these kills scorch and despawns anything else, the camera stays inside them too
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub bounds: Option<BoundsData>,
+    /// liquid fire scorch can swim in
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub pools: Vec<PoolData>,
+    /// water and rain put out scorch, embers and blocks
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub water: Vec<WaterData>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub rain: Vec<WaterData>,
+}
+
+/// Prefabs are partial entries, an entry with "prefab": "name" starts as a copy of that prefab
+/// and anything else written in the entry overrides it, even single fields inside block_info or e_info.
+/// ex: { "prefab": "wood_plank", "pos": [0.0, 0.0], "block_info": { "burn_time": 5.0 } }
+#[derive(Clone, Default, Deserialize, Serialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Prefabs {
+    pub blocks: Map<String, Value>,
+    pub enemies: Map<String, Value>,
+}
+
+/// a block in a level, size is half the width and height
+#[derive(Clone, Deserialize, Serialize)]
+#[serde(deny_unknown_fields)]
+pub struct BlockData {
+    /// the prefab this block started from
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub prefab: Option<String>,
+    /// a name for the block so triggers can refer to it
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub id: Option<String>,
+    pub pos: [f32; 2],
+    pub size: [f32; 2],
+    #[serde(default)]
+    pub block_info: BlockSpec,
+    /// ramps and polygons, see BlockShape. rects leave it out
+    /// ex: "shape": { "type": "Ramp", "high": "Right" }
+    /// ex: "shape": { "type": "Polygon", "points": [[-1.0, -1.0], [1.0, -1.0], [0.0, 1.0]] }
+    #[serde(default, skip_serializing_if = "BlockShape::is_rect")]
+    pub shape: BlockShape,
+}
+
+/// an enemy in a level, size is the radius
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[serde(deny_unknown_fields)]
+pub struct EnemyData {
+    /// the prefab this enemy started from
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub prefab: Option<String>,
+    pub pos: [f32; 2],
+    pub size: f32,
+    #[serde(default)]
+    pub e_info: EnemySpec,
+}
+
+/// the area scorch needs to reach, size is half the width and height
+#[derive(Clone, Deserialize, Serialize)]
+#[serde(deny_unknown_fields)]
+pub struct GoalData {
+    pub pos: [f32; 2],
+    pub size: [f32; 2],
+}
+
+/// the corners of the playfield, min is the bottom left and max the top right
+#[derive(Debug, Clone, Copy, Deserialize, Serialize)]
+#[serde(deny_unknown_fields)]
+pub struct BoundsData {
+    pub min: [f32; 2],
+    pub max: [f32; 2],
+}
+
+impl BoundsData {
+    pub fn to_rect(&self) -> Rect {
+        Rect::from_corners(Vec2::from(self.min), Vec2::from(self.max))
+    }
+}
+
+/// a pool of liquid fire, size is half the width and height
+#[derive(Clone, Deserialize, Serialize)]
+#[serde(deny_unknown_fields)]
+pub struct PoolData {
+    pub pos: [f32; 2],
+    pub size: [f32; 2],
+}
+
+/// water or rain, size is half the width and height
+#[derive(Clone, Deserialize, Serialize)]
+#[serde(deny_unknown_fields)]
+pub struct WaterData {
+    pub pos: [f32; 2],
+    pub size: [f32; 2],
+}
+
+/// a checkpoint in a level, scorch respawns at pos. size is half the width and height
+#[derive(Clone, Deserialize, Serialize)]
+#[serde(deny_unknown_fields)]
+pub struct CheckpointData {
+    pub pos: [f32; 2],
+    pub size: [f32; 2],
+}
+
+/// an area that runs its actions when scorch enters it. size is half the width and height
+#[derive(Clone, Deserialize, Serialize)]
+#[serde(deny_unknown_fields)]
+pub struct TriggerData {
+    pub pos: [f32; 2],
+    pub size: [f32; 2],
+    /// if false the actions run every time scorch enters, not just the first time
+    #[serde(default = "default_true")]
+    pub once: bool,
+    pub actions: Vec<TriggerAction>,
+}
+
+fn default_true() -> bool {
+    true
+}
+
+/// what a trigger does, written with the name in "action".
+/// ex: { "action": "Hint", "text": "right click to put out fires", "duration": 3.0 }
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[serde(tag = "action", deny_unknown_fields)]
+pub enum TriggerAction {
+    /// spawns the enemies, they can use the level prefabs
+    SpawnEnemies {
+        enemies: Vec<EnemyData>,
+    },
+    /// shows the text on screen for duration seconds
+    Hint {
+        text: String,
+        #[serde(default = "default_hint_duration")]
+        duration: f32,
+    },
+    /// stops the camera following scorch, it stays at pos
+    LockCamera {
+        pos: [f32; 2],
+    },
+    /// the camera follows scorch again
+    UnlockCamera,
+    /// sets the blocks with these ids on fire
+    Ignite {
+        blocks: Vec<String>,
+    },
+}
+
+fn default_hint_duration() -> f32 {
+    3.0
+}
+
+// Switches turn on and stay on. Logic gates combine them, and doors, platforms and spawners
+// take the id of a switch or gate as their input.
+// ex: burn the rope to open the gate
+//   "switches": [{ "kind": "BlockBurnt", "id": "rope_gone", "block": "rope" }],
+//   "doors": [{ "input": "rope_gone", "pos": [1250.0, 150.0], "size": [25.0, 200.0] }]
+
+/// a switch, the kind says what turns it on. size is half the width and height
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[serde(tag = "kind", deny_unknown_fields)]
+pub enum SwitchData {
+    /// turns on when scorch touches it
+    Touch {
+        id: String,
+        pos: [f32; 2],
+        size: [f32; 2],
+    },
+    /// turns on when an ember hits it
+    Ember {
+        id: String,
+        pos: [f32; 2],
+        size: [f32; 2],
+    },
+    /// turns on when the block with the id in block burns away
+    BlockBurnt {
+        id: String,
+        block: String,
+    },
+}
+
+impl SwitchData {
+    pub fn id(&self) -> &str {
+        match self {
+            SwitchData::Touch { id, .. } | SwitchData::Ember { id, .. } | SwitchData::BlockBurnt { id, .. } => id,
+        }
+    }
+}
+
+/// combines switches and other gates into a new id
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[serde(tag = "gate", deny_unknown_fields)]
+pub enum LogicData {
+    /// on when all the inputs are on
+    And {
+        id: String,
+        inputs: Vec<String>,
+    },
+    /// on when any of the inputs are on
+    Or {
+        id: String,
+        inputs: Vec<String>,
+    },
+    /// on delay seconds after the input turns on, then off again after duration if it is set
+    Timer {
+        id: String,
+        input: String,
+        #[serde(default)]
+        delay: f32,
+        #[serde(default)]
+        duration: Option<f32>,
+    },
+}
+
+impl LogicData {
+    pub fn id(&self) -> &str {
+        match self {
+            LogicData::And { id, .. } | LogicData::Or { id, .. } | LogicData::Timer { id, .. } => id,
+        }
+    }
+
+    /// the ids this gate reads
+    pub fn inputs(&self) -> Vec<&str> {
+        match self {
+            LogicData::And { inputs, .. } | LogicData::Or { inputs, .. } => {
+                inputs.iter().map(String::as_str).collect()
+            }
+            LogicData::Timer { input, .. } => vec![input.as_str()],
+        }
+    }
+}
+
+/// a solid block that opens while its input is on. size is half the width and height
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[serde(deny_unknown_fields)]
+pub struct DoorData {
+    pub input: String,
+    pub pos: [f32; 2],
+    pub size: [f32; 2],
+}
+
+/// a block that moves to `to` while its input is on, and back to pos when it is off
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[serde(deny_unknown_fields)]
+pub struct PlatformData {
+    pub input: String,
+    pub pos: [f32; 2],
+    pub size: [f32; 2],
+    pub to: [f32; 2],
+    /// how far it moves a second
+    #[serde(default = "default_platform_speed")]
+    pub speed: f32,
+}
+
+fn default_platform_speed() -> f32 {
+    200.0
+}
+
+/// spawns the enemies the first time its input turns on, they can use the level prefabs
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[serde(deny_unknown_fields)]
+pub struct SpawnerData {
+    pub input: String,
+    pub enemies: Vec<EnemyData>,
+}
+
+/// the parts of BlockInfo a level sets, anything left out comes from the material (see materials.rs)
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct BlockSpec {
+    /// the name of a material in the materials file
+    #[serde(alias = "texture")]
+    pub material: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub burnable: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub extinguishable: Option<bool>,
+    /// how long the block burns for once lit
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub burn_time: Option<f32>,
+    /// how long a fire next to it takes to spread to it, the material decides if this is not set
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub spread_delay: Option<f32>,
+    /// if scorch can push it around, it is heavier or lighter depending on the material
+    #[serde(skip_serializing_if = "std::ops::Not::not")]
+    pub movable: bool,
+    /// if it blows up when it burns out, like a powder keg
+    #[serde(skip_serializing_if = "std::ops::Not::not")]
+    pub explosive: bool,
+}
+
+impl Default for BlockSpec {
+    fn default() -> Self {
+        Self {
+            material: DEFAULT_MATERIAL.to_string(),
+            burnable: None,
+            extinguishable: None,
+            burn_time: None,
+            spread_delay: None,
+            movable: false,
+            explosive: false,
+        }
+    }
+}
+
+impl BlockSpec {
+    pub fn to_block_info(&self, materials: &MaterialRegistry) -> BlockInfo {
+        let material = materials.get(&self.material);
+        BlockInfo {
+            spread_delay: self.spread_delay,
+            movable: self.movable,
+            explosive: self.explosive,
+            ..BlockInfo::new(
+                self.burnable.unwrap_or(material.burnable),
+                self.extinguishable.unwrap_or(material.extinguishable),
+                self.burn_time.unwrap_or(material.burn_time),
+                &self.material,
+            )
+        }
+    }
+}
+
+/// the parts of EnemyInfo a level sets, anything left out uses the default.
+/// the runtime fields (cooldowns, stuns, ect) always start at their defaults
+#[derive(Debug, Clone, Copy, Deserialize, Serialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct EnemySpec {
+    pub e_type: EnemyType,
+    pub health: f32,
+    pub move_speed: f32,
+    pub dmg: f32,
+    pub range: f32,
+    pub cooldown: f32,
+    pub moveable: bool,
+}
+
+impl Default for EnemySpec {
+    fn default() -> Self {
+        let info = EnemyInfo::default();
+        Self {
+            e_type: info.e_type,
+            health: info.health,
+            move_speed: info.move_speed,
+            dmg: info.dmg,
+            range: info.range,
+            cooldown: info.cooldown,
+            moveable: info.moveable,
+        }
+    }
+}
+
+impl EnemySpec {
+    /// size comes from the EnemyData so it is not written twice
+    pub fn to_enemy_info(&self, size: f32) -> EnemyInfo {
+        EnemyInfo {
+            e_type: self.e_type,
+            health: self.health,
+            move_speed: self.move_speed,
+            dmg: self.dmg,
+            range: self.range,
+            size,
+            cooldown: self.cooldown,
+            moveable: self.moveable,
+            ..default()
+        }
+    }
+}
+
+/// why a level file or the manifest could not be loaded
+#[derive(Debug)]
+pub enum LevelLoadError {
+    /// the file could not be read
+    Io {
+        path: String,
+        err: std::io::Error,
+    },
+    /// the file is not valid level json
+    Parse {
+        path: String,
+        /// 0 if the level did not come from a level file, like imported maps.
+        /// for level files it is where the field starts in the file (see locate_field)
+        line: usize,
+        column: usize,
+        /// where in the level the error is, like blocks[3].block_info
+        field: String,
+        message: String,
+    },
+    /// the file is from a version of the game this one can not read
+    Version {
+        path: String,
+        version: u64,
+        /// the versions that can be read, older level files are migrated but old saves are not
+        oldest: u32,
+        newest: u32,
+    },
+    /// an entry uses a prefab that is not in the level
+    Prefab {
+        path: String,
+        field: String,
+        name: String,
+    },
+}
+
+impl LevelLoadError {
+    fn parse(path: &str, field: String, err: serde_json::Error) -> Self {
+        // serde puts the line and column at the end of the message, they are shown separately
+        let suffix = format!(" at line {} column {}", err.line(), err.column());
+        let message = err.to_string();
+        LevelLoadError::Parse {
+            path: path.to_string(),
+            line: err.line(),
+            column: err.column(),
+            field,
+            message: message.strip_suffix(&suffix).unwrap_or(&message).to_string(),
+        }
+    }
+}
+
+impl std::fmt::Display for LevelLoadError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            LevelLoadError::Io { path, err } => write!(f, "could not read {}: {}", path, err),
+            LevelLoadError::Parse { path, line: 0, field, message, .. } => write!(
+                f,
+                "{} at `{}`: {}",
+                path, field, message
+            ),
+            LevelLoadError::Parse { path, line, column, field, message } => write!(
+                f,
+                "{} line {}, column {} at `{}`: {}",
+                path, line, column, field, message
+            ),
+            LevelLoadError::Version { path, version, oldest, newest } if oldest == newest => write!(
+                f,
+                "{} is version {}, this game only reads version {}",
+                path, version, newest
+            ),
+            LevelLoadError::Version { path, version, newest, .. } => write!(
+                f,
+                "{} is version {}, this game only reads up to version {}",
+                path, version, newest
+            ),
+            LevelLoadError::Prefab { path, field, name } => write!(
+                f,
+                "{} at `{}`: there is no prefab named `{}`",
+                path, field, name
+            ),
+        }
+    }
+}
+
+impl std::error::Error for LevelLoadError {}
+
+/// parses json, on failure the error says where in the file and which field was wrong
+pub fn parse_json<T: DeserializeOwned>(path: &str, bytes: &[u8]) -> Result<T, LevelLoadError> {
+    let mut de = serde_json::Deserializer::from_slice(bytes);
+    let value = serde_path_to_error::deserialize(&mut de).map_err(|err| {
+        let field = err.path().to_string();
+        LevelLoadError::parse(path, field, err.into_inner())
+    })?;
+    // make sure there is nothing but whitespace after the level
+    de.end().map_err(|err| LevelLoadError::parse(path, String::from("."), err))?;
+    Ok(value)
+}
+
+/// the version of a level file, files from before versioning are version 1
+pub fn level_version(level: &Value) -> u64 {
+    level.get("version").and_then(Value::as_u64).unwrap_or(1)
+}
+
+/// parses a level file of any version, older versions are migrated to the current one
+/// and prefabs are applied to the entries that use them
+pub fn parse_level(path: &str, bytes: &[u8]) -> Result<LevelData, LevelLoadError> {
+    let mut level: Value = parse_json(path, bytes)?;
+    let version = level_version(&level);
+
+    if version == 0 || version > LEVEL_VERSION as u64 {
+        return Err(LevelLoadError::Version {
+            path: path.to_string(),
+            version,
+            oldest: 1,
+            newest: LEVEL_VERSION,
+        });
+    }
+    if version < LEVEL_VERSION as u64 {
+        migrate_level(&mut level, version);
+    }
+    resolve_level(path, level, Some(bytes))
+}
+
+/// turns level json of the current version into LevelData, this is for levels that
+/// did not come straight from a level file, like imported maps
+pub fn level_from_value(path: &str, level: Value) -> Result<LevelData, LevelLoadError> {
+    resolve_level(path, level, None)
+}
+
+/// applies the prefabs and parses the level. the level may have been changed from what is in the file so serde
+/// can not say where an error is, text is the file so the field can be found in it again
+fn resolve_level(path: &str, mut level: Value, text: Option<&[u8]>) -> Result<LevelData, LevelLoadError> {
+    apply_prefabs(path, &mut level)?;
+    serde_path_to_error::deserialize(level).map_err(|err| {
+        let field = err.path().to_string();
+        let offset = text.map(|text| (text, locate_field(text, err.path())));
+        let mut error = LevelLoadError::parse(path, field, err.into_inner());
+        if let (Some((text, offset)), LevelLoadError::Parse { line, column, .. }) = (offset, &mut error) {
+            (*line, *column) = line_and_column(text, offset);
+        }
+        error
+    })
+}
+
+/// where the value at the path starts in the json text, or as far down the path as the text goes.
+/// a field a prefab or migration added is not in the file, so the entry it is in is used instead
+fn locate_field(text: &[u8], path: &serde_path_to_error::Path) -> usize {
+    let mut pos = skip_whitespace(text, 0);
+    for segment in path.iter() {
+        let found = match segment {
+            Segment::Seq { index } => find_index(text, pos, *index),
+            Segment::Map { key } | Segment::Enum { variant: key } => find_key(text, pos, key),
+            Segment::Unknown => None,
+        };
+        match found {
+            Some(found) => pos = found,
+            None => break,
+        }
+    }
+    pos
+}
+
+/// the 1 based line and column of an offset into the text
+fn line_and_column(text: &[u8], offset: usize) -> (usize, usize) {
+    let before = &text[..offset.min(text.len())];
+    let line = before.iter().filter(|b| **b == b'\n').count() + 1;
+    let line_start = before.iter().rposition(|b| *b == b'\n').map_or(0, |i| i + 1);
+    (line, offset - line_start + 1)
+}
+
+fn skip_whitespace(text: &[u8], mut pos: usize) -> usize {
+    while pos < text.len() && text[pos].is_ascii_whitespace() {
+        pos += 1;
+    }
+    pos
+}
+
+/// the end of the string starting at pos, after the closing quote
+fn skip_string(text: &[u8], mut pos: usize) -> usize {
+    pos += 1;
+    while pos < text.len() {
+        match text[pos] {
+            b'\\' => pos += 2,
+            b'"' => return pos + 1,
+            _ => pos += 1,
+        }
+    }
+    pos
+}
+
+/// the end of the value starting at pos, the text is already known to be valid json
+fn skip_value(text: &[u8], mut pos: usize) -> usize {
+    match text.get(pos) {
+        Some(b'"') => skip_string(text, pos),
+        Some(b'{' | b'[') => {
+            let mut depth = 0;
+            while pos < text.len() {
+                match text[pos] {
+                    b'"' => {
+                        pos = skip_string(text, pos);
+                        continue;
+                    }
+                    b'{' | b'[' => depth += 1,
+                    b'}' | b']' => {
+                        depth -= 1;
+                        if depth == 0 {
+                            return pos + 1;
+                        }
+                    }
+                    _ => {}
+                }
+                pos += 1;
+            }
+            pos
+        }
+        // numbers, true, false and null
+        _ => {
+            while pos < text.len() && !matches!(text[pos], b',' | b'}' | b']') && !text[pos].is_ascii_whitespace() {
+                pos += 1;
+            }
+            pos
+        }
+    }
+}
+
+/// where the value for key starts in the object at pos
+fn find_key(text: &[u8], pos: usize, key: &str) -> Option<usize> {
+    if text.get(pos) != Some(&b'{') {
+        return None;
+    }
+    let mut pos = skip_whitespace(text, pos + 1);
+    while text.get(pos) == Some(&b'"') {
+        let key_end = skip_string(text, pos);
+        let this_key: Option<String> = serde_json::from_slice(&text[pos..key_end]).ok();
+        // past the colon
+        pos = skip_whitespace(text, skip_whitespace(text, key_end) + 1);
+        if this_key.as_deref() == Some(key) {
+            return Some(pos);
+        }
+        pos = skip_whitespace(text, skip_value(text, pos));
+        if text.get(pos) != Some(&b',') {
+            return None;
+        }
+        pos = skip_whitespace(text, pos + 1);
+    }
+    None
+}
+
+/// where the value at index starts in the array at pos
+fn find_index(text: &[u8], pos: usize, index: usize) -> Option<usize> {
+    if text.get(pos) != Some(&b'[') {
+        return None;
+    }
+    let mut pos = skip_whitespace(text, pos + 1);
+    for _ in 0..index {
+        if matches!(text.get(pos), None | Some(b']')) {
+            return None;
+        }
+        pos = skip_whitespace(text, skip_value(text, pos));
+        if text.get(pos) != Some(&b',') {
+            return None;
+        }
+        pos = skip_whitespace(text, pos + 1);
+    }
+    if matches!(text.get(pos), None | Some(b']')) {
+        return None;
+    }
+    Some(pos)
+}
+
+/// runs every migration from the version of the level up to LEVEL_VERSION
+pub fn migrate_level(level: &mut Value, from_version: u64) {
+    for migration in MIGRATIONS.iter().skip(from_version as usize - 1) {
+        migration(level);
+    }
+    if let Some(level) = level.as_object_mut() {
+        level.insert(String::from("version"), Value::from(LEVEL_VERSION));
+    }
+}
+
+/// replaces every entry that uses a prefab with the prefab, with the entry written over it.
+/// enemies spawned by triggers and spawners can use the enemy prefabs too
+fn apply_prefabs(path: &str, level: &mut Value) -> Result<(), LevelLoadError> {
+    let prefabs = level.get("prefabs").cloned().unwrap_or_default();
+    for_each_prefab_list(level, &prefabs, |prefabs, entries, field| {
+        apply_prefab_list(path, prefabs, entries, field)
+    })
+}
+
+/// calls f with every list of entries that can use prefabs, the prefabs they can use and where the list is
+fn for_each_prefab_list<E>(
+    level: &mut Value,
+    prefabs: &Value,
+    mut f: impl FnMut(&Value, &mut Value, &str) -> Result<(), E>,
+) -> Result<(), E> {
+    for list in ["blocks", "enemies"] {
+        if let Some(entries) = level.get_mut(list) {
+            f(&prefabs[list], entries, list)?;
+        }
+    }
+
+    if let Some(triggers) = level.get_mut("triggers").and_then(Value::as_array_mut) {
+        for (t, trigger) in triggers.iter_mut().enumerate() {
+            let Some(actions) = trigger.get_mut("actions").and_then(Value::as_array_mut) else {
+                continue;
+            };
+            for (a, action) in actions.iter_mut().enumerate() {
+                if let Some(entries) = action.get_mut("enemies") {
+                    let field = format!("triggers[{}].actions[{}].enemies", t, a);
+                    f(&prefabs["enemies"], entries, &field)?;
+                }
+            }
+        }
+    }
+
+    if let Some(spawners) = level.get_mut("spawners").and_then(Value::as_array_mut) {
+        for (s, spawner) in spawners.iter_mut().enumerate() {
+            if let Some(entries) = spawner.get_mut("enemies") {
+                let field = format!("spawners[{}].enemies", s);
+                f(&prefabs["enemies"], entries, &field)?;
+            }
+        }
+    }
+    Ok(())
+}
+
+/// applies the prefabs to one list of entries, field is where the list is for errors
+fn apply_prefab_list(path: &str, prefabs: &Value, entries: &mut Value, field: &str) -> Result<(), LevelLoadError> {
+    let Some(entries) = entries.as_array_mut() else {
+        return Ok(());
+    };
+    for (i, entry) in entries.iter_mut().enumerate() {
+        let Some(name) = entry.get("prefab").and_then(Value::as_str) else {
+            continue;
+        };
+        let Some(prefab) = prefabs.get(name) else {
+            return Err(LevelLoadError::Prefab {
+                path: path.to_string(),
+                field: format!("{}[{}].prefab", field, i),
+                name: name.to_string(),
+            });
+        };
+        let mut resolved = prefab.clone();
+        merge_json(&mut resolved, entry);
+        *entry = resolved;
+    }
+    Ok(())
+}
+
+/// writes over into base, objects are merged key by key so only the fields in over change.
+/// a shape of a different type replaces the whole thing, its fields would not fit the other type
+fn merge_json(base: &mut Value, over: &Value) {
+    match (base, over) {
+        (Value::Object(base), Value::Object(over)) if !different_types(base, over) => {
+            for (key, value) in over {
+                match base.get_mut(key) {
+                    Some(existing) => merge_json(existing, value),
+                    None => {
+                        base.insert(key.clone(), value.clone());
+                    }
+                }
+            }
+        }
+        (base, over) => *base = over.clone(),
+    }
+}
+
+/// if both say a type and it is not the same one, like two different shapes
+fn different_types(a: &Map<String, Value>, b: &Map<String, Value>) -> bool {
+    matches!((a.get("type"), b.get("type")), (Some(a), Some(b)) if a != b)
+}
+
+/// the level the way it is written by hand, the opposite of apply_prefabs.
+/// entries that use a prefab only keep what they change from it, so changing a prefab still changes them
+pub fn authored_level(level: &LevelData) -> Result<Value, serde_json::Error> {
+    let mut value = serde_json::to_value(level)?;
+    // the old name would be written next to the new one when an entry changes the material
+    if let Some(prefabs) = value.pointer_mut("/prefabs/blocks").and_then(Value::as_object_mut) {
+        for prefab in prefabs.values_mut() {
+            if let Some(info) = prefab.get_mut("block_info").and_then(Value::as_object_mut) {
+                if let Some(material) = info.shift_remove("texture") {
+                    info.insert(String::from("material"), material);
+                }
+            }
+        }
+    }
+
+    // compared the way the entries were written, with the fields the prefabs leave out at their defaults
+    let mut blocks = level.prefabs.blocks.clone();
+    for prefab in blocks.values_mut() {
+        normalize_field::<BlockSpec>(prefab, "block_info")?;
+        normalize_field::<BlockShape>(prefab, "shape")?;
+    }
+    let mut enemies = level.prefabs.enemies.clone();
+    for prefab in enemies.values_mut() {
+        normalize_field::<EnemySpec>(prefab, "e_info")?;
+    }
+    let prefabs = serde_json::json!({ "blocks": blocks, "enemies": enemies });
+
+    for_each_prefab_list(&mut value, &prefabs, |prefabs, entries, _| {
+        for entry in entries.as_array_mut().into_iter().flatten() {
+            let prefab = entry.get("prefab").and_then(Value::as_str).and_then(|name| prefabs.get(name));
+            if let Some(authored) = prefab.and_then(|prefab| diff_json(entry, prefab)) {
+                *entry = authored;
+            }
+        }
+        Ok(())
+    })?;
+    Ok(value)
+}
+
+/// writes the field the way it is saved, with the defaults filled in and the old names changed
+fn normalize_field<T: DeserializeOwned + Serialize>(prefab: &mut Value, key: &str) -> Result<(), serde_json::Error> {
+    if let Some(field) = prefab.get_mut(key) {
+        let parsed: T = serde_json::from_value(field.take())?;
+        *field = serde_json::to_value(parsed)?;
+    }
+    Ok(())
+}
+
+/// the parts of value that are not the same in base, the opposite of merge_json. None if there are none
+fn diff_json(value: &Value, base: &Value) -> Option<Value> {
+    match (value, base) {
+        (Value::Object(value_map), Value::Object(base_map)) if !different_types(value_map, base_map) => {
+            let mut diff = Map::new();
+            for (key, field) in value_map {
+                let changed = match base_map.get(key) {
+                    Some(base_field) => diff_json(field, base_field),
+                    None => Some(field.clone()),
+                };
+                if let Some(changed) = changed {
+                    diff.insert(key.clone(), changed);
+                }
+            }
+            // the fields the entry left out were at their defaults when it was saved,
+            // they have to be written or the prefab fills them back in
+            for (key, base_field) in base_map {
+                if !value_map.contains_key(key) {
+                    diff.insert(key.clone(), unset_value(key, base_field));
+                }
+            }
+            (!diff.is_empty()).then_some(Value::Object(diff))
+        }
+        (value, base) => (value != base).then(|| value.clone()),
+    }
+}
+
+/// what a field left out when saving is, bools are false, shapes are rects and the rest are None
+fn unset_value(key: &str, base_field: &Value) -> Value {
+    match base_field {
+        Value::Bool(_) => Value::Bool(false),
+        _ if key == "shape" => serde_json::to_value(BlockShape::Rect).unwrap_or_default(),
+        _ => Value::Null,
+    }
+}
+
+/// version 1 was the components written straight to json.
+/// burn_time was (duration, start time) and enemies had their runtime fields and a second size
+fn migrate_v1_to_v2(level: &mut Value) {
+    if let Some(blocks) = level.get_mut("blocks").and_then(Value::as_array_mut) {
+        for block in blocks {
+            if let Some(burn_time) = block.pointer_mut("/block_info/burn_time") {
+                // the start time was only ever set by the game, only the duration is kept
+                if let Some(duration) = burn_time.get(0).cloned() {
+                    *burn_time = duration;
+                }
+            }
+        }
+    }
+
+    if let Some(enemies) = level.get_mut("enemies").and_then(Value::as_array_mut) {
+        for enemy in enemies {
+            let Some(enemy) = enemy.as_object_mut() else {
+                continue;
+            };
+            let Some(e_info) = enemy.get_mut("e_info").and_then(Value::as_object_mut) else {
+                continue;
+            };
+            for runtime_field in ["active_cooldown", "stunned_until", "within_range", "damage_per_frame"] {
+                e_info.remove(runtime_field);
+            }
+            // the size was in both the enemy and its info, the enemy one was used for spawning
+            let info_size = e_info.remove("size");
+            if !enemy.contains_key("size") {
+                if let Some(size) = info_size {
+                    enemy.insert(String::from("size"), size);
+                }
+            }
+        }
+    }
+}
+
+/// version 3 added block shapes, every block before it is a rect which is what no shape means
+fn migrate_v2_to_v3(_level: &mut Value) {}
+
+/// version 4 added movable blocks, leaving it out is a block that stays put like before
+fn migrate_v3_to_v4(_level: &mut Value) {}
+
+/// version 5 added explosive blocks, nothing before it explodes
+fn migrate_v4_to_v5(_level: &mut Value) {}
+
+/// version 6 moved the block defaults into the materials file and renamed texture to material.
+/// the old defaults are written into the blocks that left them out so they stay the same,
+/// entries that use a prefab are skipped since they get the missing fields from it
+fn migrate_v5_to_v6(level: &mut Value) {
+    if let Some(prefabs) = level.pointer_mut("/prefabs/blocks").and_then(Value::as_object_mut) {
+        prefabs.values_mut().for_each(fill_v5_block_defaults);
+    }
+    if let Some(blocks) = level.get_mut("blocks").and_then(Value::as_array_mut) {
+        blocks
+            .iter_mut()
+            .filter(|block| block.get("prefab").is_none())
+            .for_each(fill_v5_block_defaults);
+    }
+}
+
+/// the block defaults from before version 6, when they were not from the material
+fn fill_v5_block_defaults(block: &mut Value) {
+    let Some(block) = block.as_object_mut() else {
+        return;
+    };
+    let Some(info) = block
+        .entry("block_info")
+        .or_insert_with(|| Value::Object(Map::new()))
+        .as_object_mut()
+    else {
+        return;
+    };
+    if let Some(texture) = info.remove("texture") {
+        info.insert(String::from("material"), texture);
+    }
+    for (field, default) in [
+        ("material", Value::from("Stone")),
+        ("burnable", Value::from(false)),
+        ("extinguishable", Value::from(false)),
+        ("burn_time", Value::from(10.0)),
+    ] {
+        info.entry(field).or_insert(default);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// the line, column and field of a parse error, panics if it is anything else
+    fn error_location(result: Result<LevelData, LevelLoadError>) -> (usize, usize, String) {
+        match result {
+            Err(LevelLoadError::Parse { line, column, field, .. }) => (line, column, field),
+            Err(err) => panic!("expected a parse error, got {}", err),
+            Ok(_) => panic!("expected a parse error, the level loaded"),
+        }
+    }
+
+    #[test]
+    fn parse_errors_have_a_line_and_column() {
+        let text = format!(
+            r#"{{
+    "version": {},
+    "blocks": [
+        {{ "pos": [0.0, 0.0], "size": "big" }}
+    ],
+    "enemies": []
+}}"#,
+            LEVEL_VERSION
+        );
+        let (line, column, field) = error_location(parse_level("test.json", text.as_bytes()));
+        assert_eq!(line, 4);
+        assert_eq!(column, text.lines().nth(3).unwrap().find("\"big\"").unwrap() + 1);
+        assert_eq!(field, "blocks[0].size");
+    }
+
+    #[test]
+    fn text_after_the_level_is_an_error() {
+        let text = "{ \"blocks\": [], \"enemies\": [] }\n}";
+        let (line, _, field) = error_location(parse_level("test.json", text.as_bytes()));
+        assert_eq!(line, 2);
+        assert_eq!(field, ".");
+    }
+
+    #[test]
+    fn newer_versions_are_refused() {
+        let text = format!(r#"{{ "version": {}, "blocks": [], "enemies": [] }}"#, LEVEL_VERSION + 1);
+        assert!(matches!(
+            parse_level("test.json", text.as_bytes()),
+            Err(LevelLoadError::Version { .. })
+        ));
+    }
+
+    #[test]
+    fn there_is_a_migration_for_every_version() {
+        assert_eq!(MIGRATIONS.len() + 1, LEVEL_VERSION as usize);
+    }
+
+    #[test]
+    fn version_1_levels_are_migrated() {
+        // version 1 had no version, burn times with a start time and the size in the enemy info too
+        let text = r#"{
+    "blocks": [
+        { "pos": [0.0, 0.0], "size": [50.0, 50.0],
+          "block_info": { "burnable": true, "extinguishable": false, "burn_time": [3.0, 0.0], "texture": "Wood" } }
+    ],
+    "enemies": [
+        { "pos": [100.0, 0.0], "size": 25.0,
+          "e_info": { "e_type": "RunDown", "health": 50.0, "move_speed": 10.0, "dmg": 10.0, "range": 0.0, "size": 30.0,
+                      "cooldown": 0.0, "active_cooldown": 1.0, "stunned_until": 0.0, "within_range": false,
+                      "damage_per_frame": 0.0, "moveable": true } }
+    ]
+}"#;
+        let level = parse_level("test.json", text.as_bytes()).unwrap();
+        assert_eq!(level.version, LEVEL_VERSION);
+
+        let info = &level.blocks[0].block_info;
+        assert_eq!(info.material, "Wood");
+        assert_eq!(info.burn_time, Some(3.0));
+        assert_eq!(info.burnable, Some(true));
+        assert_eq!(info.extinguishable, Some(false));
+
+        // the enemy size wins over the one in the info
+        assert_eq!(level.enemies[0].size, 25.0);
+        assert_eq!(level.enemies[0].e_info.health, 50.0);
+    }
+
+    #[test]
+    fn the_shipped_version_1_level_loads() {
+        let bytes = std::fs::read("assets/levels/lv1.json").unwrap();
+        assert_eq!(level_version(&serde_json::from_slice(&bytes).unwrap()), 1);
+        let level = parse_level("lv1.json", &bytes).unwrap();
+        assert_eq!(level.version, LEVEL_VERSION);
+        assert!(!level.blocks.is_empty());
+    }
+
+    #[test]
+    fn migrated_errors_point_into_the_file() {
+        let text = r#"{
+    "blocks": [
+        { "pos": [0.0, 0.0], "size": [50.0, 50.0] },
+        { "pos": [0.0, 0.0], "size": "big" }
+    ],
+    "enemies": []
+}"#;
+        let (line, column, field) = error_location(parse_level("test.json", text.as_bytes()));
+        assert_eq!(field, "blocks[1].size");
+        assert_eq!(line, 4);
+        assert_eq!(column, text.lines().nth(3).unwrap().find("\"big\"").unwrap() + 1);
+    }
+
+    #[test]
+    fn prefab_overrides_only_change_their_fields() {
+        let mut prefab = serde_json::json!({
+            "size": [50.0, 200.0],
+            "block_info": { "material": "Wood", "burnable": true, "burn_time": 3.0 },
+        });
+        let entry = serde_json::json!({
+            "prefab": "plank",
+            "pos": [10.0, 20.0],
+            "block_info": { "burn_time": 5.0 },
+        });
+        merge_json(&mut prefab, &entry);
+        assert_eq!(prefab, serde_json::json!({
+            "size": [50.0, 200.0],
+            "block_info": { "material": "Wood", "burnable": true, "burn_time": 5.0 },
+            "prefab": "plank",
+            "pos": [10.0, 20.0],
+        }));
+    }
+
+    #[test]
+    fn a_shape_of_another_type_replaces_the_prefab_shape() {
+        let mut prefab = serde_json::json!({ "shape": { "type": "Ramp", "high": "Left" } });
+        merge_json(&mut prefab, &serde_json::json!({ "shape": { "type": "Rect" } }));
+        assert_eq!(prefab, serde_json::json!({ "shape": { "type": "Rect" } }));
+
+        // the same type is merged like everything else
+        let mut prefab = serde_json::json!({ "shape": { "type": "Ramp", "high": "Left" } });
+        merge_json(&mut prefab, &serde_json::json!({ "shape": { "high": "Right" } }));
+        assert_eq!(prefab, serde_json::json!({ "shape": { "type": "Ramp", "high": "Right" } }));
+    }
+
+    #[test]
+    fn levels_apply_their_prefabs() {
+        let text = format!(
+            r#"{{
+    "version": {},
+    "prefabs": {{
+        "blocks": {{ "plank": {{ "size": [50.0, 200.0], "block_info": {{ "material": "Wood", "burn_time": 3.0 }} }} }},
+        "enemies": {{ "grunt": {{ "size": 25.0, "e_info": {{ "health": 40.0 }} }} }}
+    }},
+    "blocks": [
+        {{ "prefab": "plank", "pos": [0.0, 0.0] }},
+        {{ "prefab": "plank", "pos": [100.0, 0.0], "block_info": {{ "burn_time": 5.0 }} }}
+    ],
+    "enemies": [
+        {{ "prefab": "grunt", "pos": [0.0, 300.0] }}
+    ]
+}}"#,
+            LEVEL_VERSION
+        );
+        let level = parse_level("test.json", text.as_bytes()).unwrap();
+        assert_eq!(level.blocks[0].size, [50.0, 200.0]);
+        assert_eq!(level.blocks[0].block_info.burn_time, Some(3.0));
+        assert_eq!(level.blocks[1].block_info.burn_time, Some(5.0));
+        assert_eq!(level.blocks[1].block_info.material, "Wood");
+        assert_eq!(level.enemies[0].size, 25.0);
+        assert_eq!(level.enemies[0].e_info.health, 40.0);
+    }
+
+    #[test]
+    fn missing_prefabs_are_errors() {
+        // there is no prefabs section at all
+        let text = format!(
+            r#"{{ "version": {}, "blocks": [{{ "prefab": "nope", "pos": [0.0, 0.0] }}], "enemies": [] }}"#,
+            LEVEL_VERSION
+        );
+        match parse_level("test.json", text.as_bytes()) {
+            Err(LevelLoadError::Prefab { field, name, .. }) => {
+                assert_eq!(field, "blocks[0].prefab");
+                assert_eq!(name, "nope");
+            }
+            Err(err) => panic!("expected a prefab error, got {}", err),
+            Ok(_) => panic!("expected a prefab error, the level loaded"),
+        }
+    }
+
+    #[test]
+    fn prefab_errors_point_into_the_file() {
+        let text = format!(
+            r#"{{
+    "version": {},
+    "prefabs": {{ "blocks": {{ "plank": {{ "size": [50.0, 200.0] }} }} }},
+    "blocks": [
+        {{ "prefab": "plank", "pos": [0.0, 0.0], "block_info": {{ "burn_time": "long" }} }}
+    ],
+    "enemies": []
+}}"#,
+            LEVEL_VERSION
+        );
+        let (line, column, field) = error_location(parse_level("test.json", text.as_bytes()));
+        assert_eq!(field, "blocks[0].block_info.burn_time");
+        assert_eq!(line, 5);
+        assert_eq!(column, text.lines().nth(4).unwrap().find("\"long\"").unwrap() + 1);
+    }
+
+    #[test]
+    fn authored_levels_only_keep_what_entries_change() {
+        let bytes = std::fs::read("assets/levels/lv2.json").unwrap();
+        let mut level = parse_level("lv2.json", &bytes).unwrap();
+        let plank = level.blocks.iter().position(|block| block.prefab.is_some()).unwrap();
+        level.blocks[plank].block_info.movable = true;
+        level.blocks[plank].shape = BlockShape::Ramp { high: crate::blocks::RampSide::Left };
+
+        let authored = authored_level(&level).unwrap();
+        let entry = &authored["blocks"][plank];
+        assert!(entry.get("size").is_none());
+        assert_eq!(entry["block_info"], serde_json::json!({ "movable": true }));
+        assert_eq!(entry["shape"], serde_json::json!({ "type": "Ramp", "high": "Left" }));
+
+        // saving and loading again gives back the same level
+        let text = serde_json::to_string_pretty(&authored).unwrap();
+        let again = parse_level("lv2.json", text.as_bytes()).unwrap();
+        assert_eq!(serde_json::to_value(&again).unwrap(), serde_json::to_value(&level).unwrap());
+    }
+
+    #[test]
+    fn authored_levels_write_defaults_the_prefab_changes() {
+        let text = format!(
+            r#"{{
+    "version": {},
+    "prefabs": {{ "blocks": {{ "keg": {{ "size": [25.0, 25.0], "shape": {{ "type": "Ramp", "high": "Left" }},
+        "block_info": {{ "texture": "Wood", "burn_time": 2.0, "explosive": true }} }} }} }},
+    "blocks": [{{ "prefab": "keg", "pos": [0.0, 0.0] }}],
+    "enemies": []
+}}"#,
+            LEVEL_VERSION
+        );
+        let mut level = parse_level("test.json", text.as_bytes()).unwrap();
+        level.blocks[0].block_info.explosive = false;
+        level.blocks[0].block_info.burn_time = None;
+        level.blocks[0].shape = BlockShape::Rect;
+
+        let authored = authored_level(&level).unwrap();
+        assert_eq!(authored["blocks"][0], serde_json::json!({
+            "prefab": "keg",
+            "pos": [0.0, 0.0],
+            "block_info": { "burn_time": null, "explosive": false },
+            "shape": { "type": "Rect" },
+        }));
+        // the prefab is saved with the new name for the material, the blocks come out the same
+        assert_eq!(authored["prefabs"]["blocks"]["keg"]["block_info"]["material"], "Wood");
+        let again = parse_level("test.json", serde_json::to_string(&authored).unwrap().as_bytes()).unwrap();
+        assert_eq!(serde_json::to_value(&again.blocks).unwrap(), serde_json::to_value(&level.blocks).unwrap());
+    }
+}
@@ -0,0 +1,75 @@
+// the game is a library so other binaries, like the level linter, can use the same level types
+
+// block related aspects
+pub mod blocks;
+pub use blocks::BlockPlugin;
+// camera related aspects
+pub mod camera;
+pub use camera::CameraPlugin;
+// main charater scorch
+pub mod scorch;
+pub use scorch::ScorchPlugin;
+pub use scorch::Scorch;
+// ember
+pub mod ember;
+pub use ember::EmberPlugin;
+// collider
+pub mod coll;
+pub use coll::CollPlugin;
+// rand
+pub mod rng;
+pub use rng::RngPlugin;
+// sdtogame
+pub mod sd_to_game;
+pub use sd_to_game::SdPlugin;
+// the level file format
+pub mod level_format;
+// what blocks are made of, from the materials file
+pub mod materials;
+// Tiled map import
+pub mod tiled;
+// enemies
+pub mod enemies;
+pub use enemies::EnemyPlugin;
+// asset loader
+pub mod asset_loader;
+pub use asset_loader::AssetLoaderPlugin;
+// state system
+pub mod state_system;
+pub use state_system::StateSystemPlugin;
+// menus
+pub mod menu;
+pub use menu::MenuPlugin;
+// level editor
+pub mod editor;
+pub use editor::EditorPlugin;
+// saving and loading the world
+pub mod snapshot;
+pub use snapshot::SnapshotPlugin;
+// trigger zones placed by levels
+pub mod triggers;
+pub use triggers::TriggerPlugin;
+// switches, doors and the logic between them
+pub mod logic;
+pub use logic::LogicPlugin;
+// the procedurally generated endless mode
+pub mod endless;
+pub use endless::EndlessPlugin;
+// the edges of the level
+pub mod bounds;
+pub use bounds::BoundsPlugin;
+// liquid fire scorch can swim in
+pub mod pools;
+pub use pools::PoolPlugin;
+// water and rain that put fire out
+pub mod water;
+pub use water::WaterPlugin;
+// how burning blocks look and what they leave behind
+pub mod burning;
+pub use burning::BurnPlugin;
+// explosive blocks going off
+pub mod explosions;
+pub use explosions::ExplosionPlugin;
+// temperature, what heats things up and sets blocks on fire
+pub mod heat;
+pub use heat::HeatPlugin;
@@ -0,0 +1,391 @@
+use bevy::prelude::*;
+use bevy_rapier2d::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use std::collections::HashMap;
+
+// elsewhere in the project
+use crate::{
+    asset_loader::SceneAsset,
+    blocks::BlockBurntOut,
+    ember::EmberComponent,
+    enemies::spawn_enemy,
+    level_format::{DoorData, LevelData, LogicData, PlatformData, SpawnerData, SwitchData},
+    scorch::Scorch,
+    sd_to_game::LevelEntity,
+    state_system::AppState,
+};
+
+// Switches, logic gates and the things wired to them, see level_format for how they are written.
+// Every frame the inputs of the doors, platforms and spawners are worked out from the switches
+// and gates, so only the switches (and what the outputs have done) need to keep any state.
+
+#[derive(Bundle)]
+pub struct LogicBundle {
+    pub switch: Switch,
+}
+
+pub struct LogicPlugin;
+
+impl Plugin for LogicPlugin {
+    fn build(&self, app: &mut App) {
+        app
+            .add_systems(
+                Update,
+                (switch_contact_system, switch_burnt_system, mechanism_system)
+                    .chain()
+                    .run_if(in_state(AppState::InGame))
+            )
+        ;
+    }
+}
+
+/// gates wired in a loop would go forever, this is how deep the inputs are followed
+const MAX_LOGIC_DEPTH: usize = 32;
+
+/// how switches look before and after they are turned on
+const SWITCH_COLOR: Color = Color::srgba(1.0, 1.0, 0.0, 0.3);
+const SWITCH_ON_COLOR: Color = Color::srgba(1.0, 1.0, 0.0, 0.8);
+
+#[derive(Component, Debug, Clone, Deserialize, Serialize)]
+pub struct Switch {
+    pub data: SwitchData,
+    /// the elapsed time it turned on, None while it is off
+    pub on_since: Option<f32>,
+}
+
+#[derive(Component, Debug, Clone, Deserialize, Serialize)]
+pub struct LogicGate(pub LogicData);
+
+#[derive(Component, Debug, Clone, Deserialize, Serialize)]
+pub struct Door {
+    pub data: DoorData,
+    pub open: bool,
+}
+
+/// the position is in the transform
+#[derive(Component, Debug, Clone, Deserialize, Serialize)]
+pub struct Platform {
+    pub data: PlatformData,
+}
+
+#[derive(Component, Debug, Clone, Deserialize, Serialize)]
+pub struct Spawner {
+    pub data: SpawnerData,
+    /// spawners only go off once
+    pub spawned: bool,
+}
+
+/// every switch and gate by id, for working out what is on
+pub struct Signals<'a> {
+    switches: HashMap<&'a str, Option<f32>>,
+    gates: HashMap<&'a str, &'a LogicData>,
+}
+
+impl<'a> Signals<'a> {
+    pub fn new(
+        switches: impl Iterator<Item = &'a Switch>,
+        gates: impl Iterator<Item = &'a LogicGate>,
+    ) -> Self {
+        Signals {
+            switches: switches.map(|switch| (switch.data.id(), switch.on_since)).collect(),
+            gates: gates.map(|gate| (gate.0.id(), &gate.0)).collect(),
+        }
+    }
+
+    pub fn is_on(&self, id: &str, now: f32) -> bool {
+        self.on_since(id, now, 0).is_some()
+    }
+
+    /// when the id turned on, None if it is off or there is nothing with that id
+    fn on_since(&self, id: &str, now: f32, depth: usize) -> Option<f32> {
+        if depth > MAX_LOGIC_DEPTH {
+            return None;
+        }
+        if let Some(since) = self.switches.get(id) {
+            return *since;
+        }
+        match self.gates.get(id)? {
+            // on once the last input turned on
+            LogicData::And { inputs, .. } => {
+                let mut latest: Option<f32> = None;
+                for input in inputs {
+                    let since = self.on_since(input, now, depth + 1)?;
+                    latest = Some(latest.map_or(since, |l| l.max(since)));
+                }
+                latest
+            }
+            // on since the first input turned on
+            LogicData::Or { inputs, .. } => inputs
+                .iter()
+                .filter_map(|input| self.on_since(input, now, depth + 1))
+                .reduce(f32::min),
+            LogicData::Timer { input, delay, duration, .. } => {
+                let start = self.on_since(input, now, depth + 1)? + delay;
+                if now < start {
+                    return None;
+                }
+                if let Some(duration) = duration {
+                    if now >= start + duration {
+                        return None;
+                    }
+                }
+                Some(start)
+            }
+        }
+    }
+}
+
+/// spawns the switches, gates, doors, platforms and spawners of a level
+pub fn spawn_mechanisms(
+    commands: &mut Commands,
+    data: &LevelData,
+    asset_server: &Res<SceneAsset>,
+) {
+    for switch in &data.switches {
+        spawn_switch(commands, Switch { data: switch.clone(), on_since: None });
+    }
+    for gate in &data.logic {
+        spawn_logic_gate(commands, LogicGate(gate.clone()));
+    }
+    for door in &data.doors {
+        spawn_door(commands, Door { data: door.clone(), open: false }, asset_server);
+    }
+    for platform in &data.platforms {
+        spawn_platform(commands, Platform { data: platform.clone() }, Vec2::from(platform.pos), asset_server);
+    }
+    for spawner in &data.spawners {
+        spawn_spawner(commands, Spawner { data: spawner.clone(), spawned: false });
+    }
+}
+
+/// touch and ember switches are sensors, block burnt switches are just the Switch
+pub fn spawn_switch(
+    commands: &mut Commands,
+    switch: Switch,
+) -> Entity {
+    let area = match &switch.data {
+        // G1 is Scorch, G2 is embers, G3 is blocks, G4 is enemies, G5 is enemy_projectiles, G6 is triggers, G7 is water, G8 is debris
+        SwitchData::Touch { pos, size, .. } => Some((*pos, *size, Group::GROUP_1)),
+        SwitchData::Ember { pos, size, .. } => Some((*pos, *size, Group::GROUP_2)),
+        SwitchData::BlockBurnt { .. } => None,
+    };
+    let color = if switch.on_since.is_some() { SWITCH_ON_COLOR } else { SWITCH_COLOR };
+
+    let mut s_ent = commands.spawn((switch, LevelEntity, StateScoped(AppState::InGame)));
+    if let Some((pos, size, filter)) = area {
+        s_ent.insert((
+            SpriteBundle {
+                transform: Transform::from_xyz(pos[0], pos[1], -2.0),
+                sprite: Sprite {
+                    custom_size: Some(Vec2::from(size) * 2.0),
+                    color,
+                    ..default()
+                },
+                ..Default::default()
+            },
+            Collider::cuboid(size[0], size[1]),
+            Sensor,
+            CollisionGroups::new(Group::GROUP_6, filter),
+            ActiveEvents::COLLISION_EVENTS,
+        ));
+    }
+    s_ent.id()
+}
+
+pub fn spawn_logic_gate(
+    commands: &mut Commands,
+    gate: LogicGate,
+) -> Entity {
+    commands
+        .spawn((gate, LevelEntity, StateScoped(AppState::InGame)))
+        .id()
+}
+
+/// doors are solid like blocks but can not burn
+pub fn spawn_door(
+    commands: &mut Commands,
+    door: Door,
+    asset_server: &Res<SceneAsset>,
+) -> Entity {
+    let (pos, size) = (Vec2::from(door.data.pos), Vec2::from(door.data.size));
+    let open = door.open;
+    let mut d_ent = commands.spawn((
+        SpriteBundle {
+            texture: asset_server.t_block_unburnable.clone(),
+            transform: Transform::from_xyz(pos.x, pos.y, -1.0),
+            sprite: Sprite {
+                custom_size: Some(size * 2.0),
+                color: Color::srgb(0.6, 0.6, 0.6),
+                ..default()
+            },
+            ..Default::default()
+        },
+        Collider::cuboid(size.x, size.y),
+        CollisionGroups::new(
+            // G1 is Scorch, G2 is embers, G3 is blocks, G4 is enemies, G5 is enemy_projectiles, G6 is triggers, G7 is water, G8 is debris
+            // the same filter as blocks so movable blocks and debris land on closed doors
+            Group::GROUP_3,
+            Group::GROUP_1 | Group::GROUP_2 | Group::GROUP_3 | Group::GROUP_4 | Group::GROUP_5 | Group::GROUP_7 | Group::GROUP_8,
+        ),
+        door,
+        LevelEntity,
+        StateScoped(AppState::InGame),
+    ));
+    if open {
+        d_ent.insert((ColliderDisabled, Visibility::Hidden));
+    }
+    d_ent.id()
+}
+
+/// platforms are moved by setting their transform, so they push scorch but are not pushed back
+pub fn spawn_platform(
+    commands: &mut Commands,
+    platform: Platform,
+    pos: Vec2,
+    asset_server: &Res<SceneAsset>,
+) -> Entity {
+    let size = Vec2::from(platform.data.size);
+    commands
+        .spawn((
+            SpriteBundle {
+                texture: asset_server.t_block_unburnable.clone(),
+                transform: Transform::from_xyz(pos.x, pos.y, -1.0),
+                sprite: Sprite {
+                    custom_size: Some(size * 2.0),
+                    ..default()
+                },
+                ..Default::default()
+            },
+            RigidBody::KinematicPositionBased,
+            Collider::cuboid(size.x, size.y),
+            CollisionGroups::new(
+                // G1 is Scorch, G2 is embers, G3 is blocks, G4 is enemies, G5 is enemy_projectiles, G6 is triggers, G7 is water, G8 is debris
+                Group::GROUP_3,
+                Group::GROUP_1 | Group::GROUP_2 | Group::GROUP_3 | Group::GROUP_4 | Group::GROUP_5 | Group::GROUP_7 | Group::GROUP_8,
+            ),
+            platform,
+            LevelEntity,
+            StateScoped(AppState::InGame),
+        ))
+        .id()
+}
+
+pub fn spawn_spawner(
+    commands: &mut Commands,
+    spawner: Spawner,
+) -> Entity {
+    commands
+        .spawn((spawner, LevelEntity, StateScoped(AppState::InGame)))
+        .id()
+}
+
+/// turns on touch switches when scorch touches them and ember switches when an ember hits them
+fn switch_contact_system(
+    mut collision_events: EventReader<CollisionEvent>,
+    time: Res<Time>,
+    mut switch_query: Query<(&mut Switch, &mut Sprite)>,
+    scorch_query: Query<(), With<Scorch>>,
+    ember_query: Query<(), With<EmberComponent>>,
+) {
+    for c_event in collision_events.read() {
+        let CollisionEvent::Started(e1, e2, _) = c_event else {
+            continue;
+        };
+        // either one could be the switch
+        let (s_ent, other) = if switch_query.contains(*e1) { (*e1, *e2) } else { (*e2, *e1) };
+        let Ok((mut switch, mut sprite)) = switch_query.get_mut(s_ent) else {
+            continue;
+        };
+        if switch.on_since.is_some() {
+            continue;
+        }
+        let hit = match switch.data {
+            SwitchData::Touch { .. } => scorch_query.contains(other),
+            SwitchData::Ember { .. } => ember_query.contains(other),
+            SwitchData::BlockBurnt { .. } => false,
+        };
+        if hit {
+            println!("Switch {} on", switch.data.id());
+            switch.on_since = Some(time.elapsed_seconds());
+            sprite.color = SWITCH_ON_COLOR;
+        }
+    }
+}
+
+/// turns on the switches watching a block when it burns away
+fn switch_burnt_system(
+    mut burnt_events: EventReader<BlockBurntOut>,
+    time: Res<Time>,
+    mut switch_query: Query<&mut Switch>,
+) {
+    for event in burnt_events.read() {
+        let Some(burnt) = &event.id else {
+            continue;
+        };
+        for mut switch in switch_query.iter_mut() {
+            if let SwitchData::BlockBurnt { block, .. } = &switch.data {
+                if block == burnt && switch.on_since.is_none() {
+                    println!("Switch {} on", switch.data.id());
+                    switch.on_since = Some(time.elapsed_seconds());
+                }
+            }
+        }
+    }
+}
+
+/// opens doors, moves platforms and runs spawners based on their inputs
+fn mechanism_system(
+    mut commands: Commands,
+    time: Res<Time>,
+    asset_server: Res<SceneAsset>,
+    switch_query: Query<&Switch>,
+    gate_query: Query<&LogicGate>,
+    mut door_query: Query<(Entity, &mut Door, &mut Visibility)>,
+    mut platform_query: Query<(&Platform, &mut Transform)>,
+    mut spawner_query: Query<&mut Spawner>,
+) {
+    let now = time.elapsed_seconds();
+    let signals = Signals::new(switch_query.iter(), gate_query.iter());
+
+    for (entity, mut door, mut visibility) in door_query.iter_mut() {
+        let open = signals.is_on(&door.data.input, now);
+        if open == door.open {
+            continue;
+        }
+        door.open = open;
+        if open {
+            commands.entity(entity).insert(ColliderDisabled);
+            *visibility = Visibility::Hidden;
+        } else {
+            commands.entity(entity).remove::<ColliderDisabled>();
+            *visibility = Visibility::Inherited;
+        }
+    }
+
+    for (platform, mut transform) in platform_query.iter_mut() {
+        let data = &platform.data;
+        let target = if signals.is_on(&data.input, now) { data.to } else { data.pos };
+        let pos = transform.translation.truncate();
+        let step = data.speed * time.delta_seconds();
+        let new_pos = pos + (Vec2::from(target) - pos).clamp_length_max(step);
+        transform.translation = new_pos.extend(transform.translation.z);
+    }
+
+    for mut spawner in spawner_query.iter_mut() {
+        if spawner.spawned || !signals.is_on(&spawner.data.input, now) {
+            continue;
+        }
+        spawner.spawned = true;
+        for enemy in &spawner.data.enemies {
+            let e_ent = spawn_enemy(
+                &mut commands,
+                Vec2::from(enemy.pos),
+                enemy.e_info.to_enemy_info(enemy.size),
+                enemy.size,
+                &asset_server,
+            );
+            commands.entity(e_ent).insert(LevelEntity);
+        }
+    }
+}
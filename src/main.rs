@@ -3,37 +3,31 @@ use bevy::prelude::*;
 use bevy_rapier2d::prelude::*;
 use bevy::dev_tools::fps_overlay::FpsOverlayPlugin;
 
-// block related aspects
-mod blocks;
-use blocks::BlockPlugin;
-// camera related aspects
-mod camera;
-use camera::CameraPlugin;
-// main charater scorch
-mod scorch;
-use scorch::ScorchPlugin;
-use scorch::Scorch;
-// ember
-mod ember;
-use ember::EmberPlugin;
-// collider
-mod coll;
-use coll::CollPlugin;
-// rand
-mod rng;
-use rng::RngPlugin;
-// sdtogame
-mod sd_to_game;
-use sd_to_game::SdPlugin;
-// enemies
-mod enemies;
-use enemies::EnemyPlugin;
-// asset loader
-mod asset_loader;
-use asset_loader::AssetLoaderPlugin;
-// state system
-mod state_system;
-use state_system::StateSystemPlugin;
+// home made plugins, the modules themselves are in lib.rs
+use fire_swimmer::{
+    AssetLoaderPlugin,
+    BlockPlugin,
+    BoundsPlugin,
+    BurnPlugin,
+    CameraPlugin,
+    CollPlugin,
+    EditorPlugin,
+    EmberPlugin,
+    EndlessPlugin,
+    EnemyPlugin,
+    ExplosionPlugin,
+    HeatPlugin,
+    LogicPlugin,
+    MenuPlugin,
+    PoolPlugin,
+    RngPlugin,
+    ScorchPlugin,
+    SdPlugin,
+    SnapshotPlugin,
+    StateSystemPlugin,
+    TriggerPlugin,
+    WaterPlugin,
+};
 
 fn main() {
     App::new()
@@ -54,6 +48,18 @@ fn main() {
         .add_plugins(RngPlugin)
         .add_plugins(SdPlugin)
         .add_plugins(EnemyPlugin)
+        .add_plugins(MenuPlugin)
+        .add_plugins(EditorPlugin)
+        .add_plugins(SnapshotPlugin)
+        .add_plugins(TriggerPlugin)
+        .add_plugins(LogicPlugin)
+        .add_plugins(EndlessPlugin)
+        .add_plugins(BoundsPlugin)
+        .add_plugins(PoolPlugin)
+        .add_plugins(WaterPlugin)
+        .add_plugins(BurnPlugin)
+        .add_plugins(ExplosionPlugin)
+        .add_plugins(HeatPlugin)
         // TODO move to a scheduling system
         .run();
 }
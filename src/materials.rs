@@ -0,0 +1,139 @@
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use std::collections::{BTreeMap, HashMap};
+
+// elsewhere in the project
+use crate::{
+    burning::BurnOut,
+    level_format::{parse_json, LevelLoadError},
+};
+
+// What blocks are made of comes from the materials file instead of being written into the game.
+// Each material has its texture, how it burns and how it moves, blocks name the material they use
+// and only write the parts they change (see level_format::BlockSpec).
+// The file is read once when the game starts, the textures are loaded with the rest in asset_loader.
+// ex: "Coal": { "texture": "sprites/t_slate.png", "color": [0.3, 0.3, 0.3], "burnable": true, "ignition_temp": 400.0, ... }
+
+/// where the materials are kept
+pub const MATERIALS_FILE: &str = "assets/materials.json";
+
+/// the material blocks use when they do not name one
+pub const DEFAULT_MATERIAL: &str = "Stone";
+
+#[derive(Deserialize, Serialize)]
+#[serde(deny_unknown_fields)]
+struct MaterialsFile {
+    materials: BTreeMap<String, Material>,
+}
+
+/// one material from the materials file
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[serde(deny_unknown_fields)]
+pub struct Material {
+    /// path to the sprite, relative to the assets folder
+    pub texture: String,
+    /// multiplied with the sprite, so one sprite can be used for a few materials
+    #[serde(default = "white")]
+    pub color: [f32; 3],
+    /// if it can be set on fire
+    pub burnable: bool,
+    /// if scorch can put it out once it is lit
+    pub extinguishable: bool,
+    /// how long it burns for once lit
+    pub burn_time: f32,
+    /// how hot it has to get to catch fire, see heat.rs
+    #[serde(default = "default_ignition_temp")]
+    pub ignition_temp: f32,
+    /// how long a fire next to it takes to spread to it, None if fire never spreads to it
+    #[serde(default)]
+    pub spread_delay: Option<f32>,
+    /// what is left once it burns out
+    pub burn_out: BurnOut,
+    pub friction: f32,
+    /// how bouncy it is
+    pub restitution: f32,
+    /// how heavy movable blocks are for their size, scorch is 1.0
+    pub density: f32,
+}
+
+fn white() -> [f32; 3] {
+    [1.0, 1.0, 1.0]
+}
+
+fn default_ignition_temp() -> f32 {
+    300.0
+}
+
+impl Default for Material {
+    /// what a block with a material that is not in the file is made of, it is drawn plain white
+    fn default() -> Self {
+        Self {
+            texture: String::from("sprites/t_temp.png"),
+            color: white(),
+            burnable: false,
+            extinguishable: false,
+            burn_time: 10.0,
+            ignition_temp: default_ignition_temp(),
+            spread_delay: None,
+            burn_out: BurnOut::Vanish,
+            friction: 0.5,
+            restitution: 0.0,
+            density: 1.0,
+        }
+    }
+}
+
+impl Material {
+    pub fn color(&self) -> Color {
+        Color::srgb(self.color[0], self.color[1], self.color[2])
+    }
+}
+
+/// every material by name, and their textures once asset_loader has loaded them
+#[derive(Debug, Default, Clone)]
+pub struct MaterialRegistry {
+    pub materials: BTreeMap<String, Material>,
+    pub textures: HashMap<String, Handle<Image>>,
+    fallback: Material,
+}
+
+impl MaterialRegistry {
+    pub fn new(materials: BTreeMap<String, Material>) -> Self {
+        Self { materials, ..default() }
+    }
+
+    pub fn contains(&self, name: &str) -> bool {
+        self.materials.contains_key(name)
+    }
+
+    /// the material with the name, a placeholder if there is none (the lint reports those)
+    pub fn get(&self, name: &str) -> &Material {
+        self.materials.get(name).unwrap_or(&self.fallback)
+    }
+
+    /// the sprite for the material, the default handle if it is not loaded
+    pub fn texture(&self, name: &str) -> Handle<Image> {
+        self.textures.get(name).cloned().unwrap_or_default()
+    }
+
+    /// the names in order, the editor cycles through them
+    pub fn names(&self) -> Vec<&str> {
+        self.materials.keys().map(String::as_str).collect()
+    }
+}
+
+/// parses the materials file
+pub fn parse_materials(path: &str, bytes: &[u8]) -> Result<MaterialRegistry, LevelLoadError> {
+    let file: MaterialsFile = parse_json(path, bytes)?;
+    Ok(MaterialRegistry::new(file.materials))
+}
+
+/// reads the materials file, the textures still need to be loaded
+pub fn read_materials() -> Result<MaterialRegistry, LevelLoadError> {
+    let bytes = std::fs::read(MATERIALS_FILE).map_err(|err| LevelLoadError::Io {
+        path: MATERIALS_FILE.to_string(),
+        err,
+    })?;
+    parse_materials(MATERIALS_FILE, &bytes)
+}
@@ -0,0 +1,159 @@
+use bevy::prelude::*;
+//use bevy_rapier2d::prelude::*;
+
+// elsewhere in the project
+use crate::{
+    endless::start_endless_run,
+    rng::RngResource,
+    sd_to_game::{read_level_manifest, CurrentLevel, LevelLoadFailure, LevelRegistry},
+    state_system::AppState,
+};
+
+#[derive(Bundle)]
+pub struct MenuBundle {
+    // unused
+}
+
+pub struct MenuPlugin;
+
+impl Plugin for MenuPlugin {
+    fn build(&self, app: &mut App) {
+        app
+            .add_systems(OnEnter(AppState::MainMenu), spawn_main_menu)
+            .add_systems(OnEnter(AppState::LevelError), spawn_level_error)
+            .add_systems(
+                Update,
+                (main_menu_input).run_if(in_state(AppState::MainMenu))
+            )
+            .add_systems(
+                Update,
+                (level_error_input).run_if(in_state(AppState::LevelError))
+            )
+        ;
+    }
+}
+
+/// a full screen column of text lines, used for all the menus
+fn spawn_text_screen(
+    commands: &mut Commands,
+    state: AppState,
+    lines: Vec<(String, f32)>,
+) {
+    commands
+        .spawn((
+            NodeBundle {
+                style: Style {
+                    width: Val::Percent(100.0),
+                    height: Val::Percent(100.0),
+                    flex_direction: FlexDirection::Column,
+                    align_items: AlignItems::Center,
+                    justify_content: JustifyContent::Center,
+                    row_gap: Val::Px(20.0),
+                    ..default()
+                },
+                ..default()
+            },
+            StateScoped(state),
+        ))
+        .with_children(|parent| {
+            for (text, font_size) in lines {
+                parent.spawn(
+                    TextBundle::from_section(
+                        text,
+                        TextStyle {
+                            font_size,
+                            color: Color::WHITE,
+                            ..default()
+                        },
+                    )
+                    .with_text_justify(JustifyText::Center)
+                    .with_style(Style {
+                        max_width: Val::Percent(80.0),
+                        ..default()
+                    }),
+                );
+            }
+        });
+}
+
+fn spawn_main_menu(
+    mut commands: Commands,
+) {
+    spawn_text_screen(
+        &mut commands,
+        AppState::MainMenu,
+        vec![
+            (String::from("Fire Swimmer"), 60.0),
+            (String::from("Press Enter to play"), 30.0),
+            (String::from("Press E to edit the level"), 30.0),
+            (String::from("Press L to load the saved game"), 30.0),
+            (String::from("Press N for endless mode"), 30.0),
+        ],
+    );
+}
+
+fn main_menu_input(
+    mut commands: Commands,
+    key_presses: Res<ButtonInput<KeyCode>>,
+    mut rng: ResMut<RngResource>,
+    mut next_state: ResMut<NextState<AppState>>,
+) {
+    if key_presses.just_pressed(KeyCode::Enter) {
+        next_state.set(AppState::InGame);
+    } else if key_presses.just_pressed(KeyCode::KeyE) {
+        next_state.set(AppState::Editor);
+    } else if key_presses.just_pressed(KeyCode::KeyN) {
+        start_endless_run(&mut commands, &mut rng);
+        next_state.set(AppState::InGame);
+    }
+}
+
+fn spawn_level_error(
+    mut commands: Commands,
+    failure: Option<Res<LevelLoadFailure>>,
+) {
+    let message = failure
+        .map(|f| f.message.clone())
+        .unwrap_or_else(|| String::from("unknown error"));
+
+    spawn_text_screen(
+        &mut commands,
+        AppState::LevelError,
+        vec![
+            (String::from("Could not load the level"), 40.0),
+            (message, 20.0),
+            (String::from("R to retry, Escape to return to the menu"), 30.0),
+        ],
+    );
+}
+
+/// R reloads the level files and tries again, Escape goes back to the main menu
+fn level_error_input(
+    mut commands: Commands,
+    key_presses: Res<ButtonInput<KeyCode>>,
+    asset_server: Res<AssetServer>,
+    mut registry: ResMut<LevelRegistry>,
+    current: Res<CurrentLevel>,
+    mut next_state: ResMut<NextState<AppState>>,
+) {
+    if key_presses.just_pressed(KeyCode::KeyR) {
+        commands.remove_resource::<LevelLoadFailure>();
+
+        // the manifest might have been the problem so read it again
+        match read_level_manifest() {
+            Ok(levels) => registry.levels = levels,
+            Err(err) => {
+                // with no levels the game comes straight back here with this message
+                println!("ERROR: {}", err);
+                registry.levels.clear();
+                commands.insert_resource(LevelLoadFailure { message: err.to_string() });
+            }
+        }
+        if let Some(entry) = registry.get(current.index) {
+            asset_server.reload(entry.path.clone());
+        }
+        next_state.set(AppState::InGame);
+    } else if key_presses.just_pressed(KeyCode::Escape) {
+        next_state.set(AppState::MainMenu);
+    }
+}
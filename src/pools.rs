@@ -0,0 +1,186 @@
+use bevy::prelude::*;
+use bevy_rapier2d::prelude::*;
+
+// elsewhere in the project
+use crate::{
+    asset_loader::SceneAsset,
+    ember::EmberComponent,
+    enemies::EnemyInfo,
+    scorch::Scorch,
+    sd_to_game::LevelEntity,
+    state_system::AppState,
+};
+
+// Fire pools are areas of liquid fire from the level file, scorch can swim through them.
+// Anything with its center inside a pool is Submerged, that swaps its gravity and damping for the pool's
+// and buoyancy pushes it up towards the surface.
+// Scorch swims with WASD (see scorch::character_movement) and gets flame back while submerged,
+// enemies get burned and embers get rekindled, they float back out at full strength since they are lighter than the fire.
+
+#[derive(Bundle)]
+pub struct PoolBundle {
+    pub pool: FirePool,
+}
+
+pub struct PoolPlugin;
+
+impl Plugin for PoolPlugin {
+    fn build(&self, app: &mut App) {
+        app
+            .add_systems(
+                Update,
+                (submerge_system, buoyancy_system, pool_effects).chain().run_if(in_state(AppState::InGame))
+            )
+        ;
+    }
+}
+
+/// the color pools are drawn with, the editor uses it too
+pub const POOL_COLOR: Color = Color::srgba(1.0, 0.3, 0.0, 0.5);
+
+/// the gravity scale of anything in a pool
+const POOL_GRAVITY_SCALE: f32 = 0.1;
+/// the linear damping of anything in a pool, so swimming feels thick
+const POOL_DAMPING: f32 = 3.0;
+/// embers have no Velocity for buoyancy to push on, so their gravity is flipped instead
+const EMBER_POOL_GRAVITY_SCALE: f32 = -0.5;
+/// how much velocity buoyancy adds per second, at full depth
+const BUOYANCY: f32 = 400.0;
+/// how far under the surface buoyancy is at full strength
+const FULL_DEPTH: f32 = 100.0;
+/// flame scorch gets back per second while submerged, on top of the normal regen
+const POOL_REGEN: f32 = 20.0;
+/// damage per second enemies take while submerged
+const POOL_ENEMY_DPS: f32 = 25.0;
+
+/// a pool of liquid fire, size is half the width and height
+#[derive(Component, Debug, Clone, Copy)]
+pub struct FirePool {
+    pub size: Vec2,
+}
+
+/// on anything inside a pool, keeps what it had before so it can be put back when it leaves
+#[derive(Component, Debug, Clone, Copy)]
+pub struct Submerged {
+    /// the top of the pool it is in
+    pub surface: f32,
+    gravity: f32,
+    damping: f32,
+}
+
+/// spawns a fire pool, size is half the width and height
+pub fn spawn_pool(
+    commands: &mut Commands,
+    pos: Vec2,
+    size: Vec2,
+    asset_server: &Res<SceneAsset>,
+) -> Entity {
+    commands
+        .spawn((
+            SpriteBundle {
+                texture: asset_server.t_temp.clone(),
+                // in front of scorch so it looks like they are in it
+                transform: Transform::from_xyz(pos.x, pos.y, 0.5),
+                sprite: Sprite {
+                    custom_size: Some(size * 2.0),
+                    color: POOL_COLOR,
+                    ..default()
+                },
+                ..Default::default()
+            },
+            FirePool { size },
+            LevelEntity,
+            StateScoped(AppState::InGame),
+        ))
+        .id()
+}
+
+/// the surface of the pool pos is in, if it is in one
+fn pool_at(pos: Vec2, pool_query: &Query<(&FirePool, &Transform)>) -> Option<f32> {
+    pool_query.iter().find_map(|(pool, trans)| {
+        let center = trans.translation.truncate();
+        Rect::from_center_half_size(center, pool.size)
+            .contains(pos)
+            .then_some(center.y + pool.size.y)
+    })
+}
+
+/// adds Submerged to things that went into a pool and takes it off things that left
+fn submerge_system(
+    mut commands: Commands,
+    pool_query: Query<(&FirePool, &Transform)>,
+    mut query: Query<
+        (
+            Entity,
+            &Transform,
+            Option<&GravityScale>,
+            Option<&Damping>,
+            Option<&mut Submerged>,
+            Has<EmberComponent>,
+        ),
+        Or<(With<Scorch>, With<EnemyInfo>, With<EmberComponent>)>,
+    >,
+) {
+    for (entity, trans, gravity, damping, submerged, is_ember) in query.iter_mut() {
+        match (pool_at(trans.translation.truncate(), &pool_query), submerged) {
+            // it could have moved from one pool into another
+            (Some(surface), Some(mut submerged)) => submerged.surface = surface,
+            (Some(surface), None) => {
+                commands.entity(entity).insert((
+                    Submerged {
+                        surface,
+                        // no component is the same as the defaults
+                        gravity: gravity.map_or(1.0, |g| g.0),
+                        damping: damping.map_or(0.0, |d| d.linear_damping),
+                    },
+                    GravityScale(if is_ember { EMBER_POOL_GRAVITY_SCALE } else { POOL_GRAVITY_SCALE }),
+                    Damping { linear_damping: POOL_DAMPING, angular_damping: 0.0 },
+                ));
+            }
+            (None, Some(submerged)) => {
+                commands.entity(entity)
+                    .insert((
+                        GravityScale(submerged.gravity),
+                        Damping { linear_damping: submerged.damping, angular_damping: 0.0 },
+                    ))
+                    .remove::<Submerged>();
+            }
+            (None, None) => {}
+        }
+    }
+}
+
+/// pushes submerged things up, more the deeper they are
+fn buoyancy_system(
+    time: Res<Time>,
+    mut query: Query<(&Submerged, &Transform, &mut Velocity)>,
+) {
+    for (submerged, trans, mut velocity) in query.iter_mut() {
+        let depth = ((submerged.surface - trans.translation.y) / FULL_DEPTH).clamp(0.0, 1.0);
+        velocity.linvel.y += BUOYANCY * depth * time.delta_seconds();
+    }
+}
+
+/// scorch regains flame, enemies burn and embers are rekindled
+fn pool_effects(
+    mut commands: Commands,
+    time: Res<Time>,
+    mut s_query: Query<&mut Scorch, With<Submerged>>,
+    mut e_query: Query<(Entity, &mut EnemyInfo), With<Submerged>>,
+    mut em_query: Query<&mut EmberComponent, With<Submerged>>,
+) {
+    let dt = time.delta_seconds();
+    for mut s_info in s_query.iter_mut() {
+        s_info.curr_flame = (s_info.curr_flame + POOL_REGEN * dt).min(s_info.max_flame);
+    }
+    for (entity, mut en_info) in e_query.iter_mut() {
+        if en_info.take_dmg(POOL_ENEMY_DPS * dt) {
+            //this happens when the enemy is dead
+            en_info.death_effect();
+            commands.entity(entity).despawn();
+        }
+    }
+    for mut ember in em_query.iter_mut() {
+        *ember = EmberComponent::full();
+    }
+}
@@ -25,9 +25,28 @@ impl Plugin for RngPlugin {
 #[derive(Resource)]
 pub struct RngResource {
     pub rng: rand::rngs::SmallRng,
+    /// what rng was seeded with, so a run can be played again
+    pub seed: u64,
 }
 impl Default for RngResource {
     fn default() -> Self {
-        Self { rng: SmallRng::from_entropy(), }
+        Self::new(rand::random())
+    }
+}
+
+impl RngResource {
+    pub fn new(seed: u64) -> Self {
+        Self { rng: SmallRng::seed_from_u64(seed), seed }
+    }
+
+    /// starts over from a new seed
+    pub fn reseed(&mut self, seed: u64) {
+        *self = Self::new(seed);
+    }
+
+    /// a separate rng for one part of the game, like a chunk of the endless mode.
+    /// it only depends on the seed and the index, not on how much rng has been used
+    pub fn stream(&self, index: u64) -> SmallRng {
+        SmallRng::seed_from_u64(self.seed ^ index.wrapping_mul(0x9E37_79B9_7F4A_7C15))
     }
 }
\ No newline at end of file
@@ -2,6 +2,7 @@ use bevy::prelude::*;
 use bevy_rapier2d::prelude::*;
 
 use rand::Rng;
+use serde::{Deserialize, Serialize};
 // use rand::SeedableRng;
 // use rand::rngs::SmallRng;
 use bevy::window::PrimaryWindow;
@@ -12,7 +13,10 @@ use crate::{
     blocks::BlockInfo, 
     coll::DebugComp, 
     ember, 
+    heat::{Temperature, AMBIENT_TEMP, SCORCH_TEMP},
+    pools::Submerged,
     rng::RngResource,
+    sd_to_game::RespawnPoint,
     state_system::AppState,
 };
 
@@ -58,7 +62,10 @@ const DASH_COOLDOWN: f32 = 1.0;
 /// How long between presses would make a dash or something else
 const DOUBLE_TAP_COOLDOWN: f32 = 0.2;
 
-#[derive(Component, Clone, Copy, PartialEq)]
+/// how much velocity swimming adds each frame a key is held
+const SWIM_SPEED: f32 = 15.0;
+
+#[derive(Component, Debug, Clone, Copy, PartialEq, Deserialize, Serialize)]
 #[allow(dead_code)]
 pub struct Scorch {
     /// max life for scorch
@@ -91,6 +98,28 @@ pub struct Scorch {
     pub is_dead: bool,
 }
 
+/// scorch as it is at the start of a level
+impl Default for Scorch {
+    fn default() -> Self {
+        Self {
+            max_flame: 100.0,
+            curr_flame: 100.0,
+
+            i_frame: 1.0,
+            i_frame_timer: 0.0,
+
+            double_jump: false,
+
+            dash: (false, 0.0),
+            a_dash: 0.0,
+            d_dash: 0.0,
+
+            damage_per_frame: 0.0,
+            is_dead: false,
+        }
+    }
+}
+
 #[allow(dead_code)]
 impl Scorch {
     pub fn regen_flame(&mut self) {
@@ -228,9 +257,9 @@ fn setup_physics(
             RigidBody::Dynamic,
             Collider::ball(50.0),
             CollisionGroups::new(
-                // G1 is Scorch, G2 is embers, G3 is blocks, G4 is enemies, G5 is enemy_projectiles
+                // G1 is Scorch, G2 is embers, G3 is blocks, G4 is enemies, G5 is enemy_projectiles, G6 is triggers, G7 is water, G8 is debris
                 Group::GROUP_1,
-                Group::GROUP_2 | Group::GROUP_3 | Group::GROUP_4 | Group::GROUP_5,
+                Group::GROUP_2 | Group::GROUP_3 | Group::GROUP_4 | Group::GROUP_5 | Group::GROUP_6 | Group::GROUP_7,
             ),
             ActiveEvents::COLLISION_EVENTS,
             Restitution::coefficient(0.1),
@@ -253,22 +282,9 @@ fn setup_physics(
                 linear_damping: 0.1, 
                 angular_damping: 0.0
             },
-            Scorch {
-                max_flame: 100.0,
-                curr_flame: 100.0,
-
-                i_frame: 1.0,
-                i_frame_timer: 0.0,
-
-                double_jump: false,
-
-                dash: (false, 0.0),
-                a_dash: 0.0,
-                d_dash: 0.0,
-
-                damage_per_frame: 0.0,
-                is_dead: false,
-            },
+            Scorch::default(),
+            Temperature::new(SCORCH_TEMP),
+            StateScoped(AppState::InGame),
         ))
         .with_children(|parent| {
             // Add the sensor collider as a child
@@ -325,7 +341,7 @@ fn propell_scorch(
     // for raycasting for exstinguishing
     rc: Res<RapierContext>,
     // query for blocks with block info for extinguish
-    mut bi_query: Query<&mut BlockInfo>,
+    mut bi_query: Query<(&mut BlockInfo, &mut Temperature)>,
     //for spawning embers with the right texture
     asset_server: Res<SceneAsset>,
 
@@ -402,10 +418,12 @@ fn propell_scorch(
                     QueryFilter::default(),
                     ) {
                         // if let check for the block having block info
-                        if let Ok(mut ex_block) = bi_query.get_mut(*ext_entity) {
-                            if ex_block.extinguishable && ex_block.burn_time.1 != 0.0 {
+                        if let Ok((mut ex_block, mut ex_temp)) = bi_query.get_mut(*ext_entity) {
+                            if ex_block.extinguishable && ex_block.is_burning() {
                                 //println!("extinguish block!");
-                                ex_block.burn_time.1 = 0.0;
+                                ex_block.extinguish();
+                                // cooled down too or it would catch again straight away
+                                ex_temp.cool_to(AMBIENT_TEMP);
                             } else if ex_block.extinguishable {
                                 //println!("block not on fire!");
                             } else {
@@ -458,15 +476,20 @@ fn propell_scorch(
 }
 
 #[allow(irrefutable_let_patterns, dead_code, unused_mut)]
-/// when the R key is pressed it resets it to the starting position
+/// when the R key is pressed it resets it to the last checkpoint, or the spawn of the level.
+/// shift+R resets the whole level instead, that is reset_level in sd_to_game
 fn restart_scorch(
     mut commands: Commands,
     mut s_query: Query<(&mut Scorch, &mut ExternalImpulse, &mut Velocity, &mut Transform)>,
     key_presses: Res<ButtonInput<KeyCode>>,
     asset_server: Res<SceneAsset>, //TODO make functions not need to call this
+    respawn: Res<RespawnPoint>,
 ) {
     // let (s_entity, mut s_compo) = scor_query.single_mut();
-    if key_presses.just_pressed(KeyCode::KeyR) {
+    if 
+        key_presses.just_pressed(KeyCode::KeyR) 
+        && !key_presses.any_pressed([KeyCode::ShiftLeft, KeyCode::ShiftRight]) 
+    {
         // I swear if scorch DNE then this should fail just in case I will leave this, and add the allow
         if let (
             mut s_info, 
@@ -474,10 +497,7 @@ fn restart_scorch(
             mut s_velocity, 
             mut s_position
         ) = s_query.single_mut() {
-            s_position.translation = Vec3::new(0.0, 0.0, -1.0);
-            s_impulse.impulse = Vec2::ZERO;
-            s_velocity.linvel = Vec2::ZERO;
-            s_info.reset();
+            respawn_scorch(&mut s_info, &mut s_impulse, &mut s_velocity, &mut s_position, respawn.pos);
         } else {
             setup_physics(commands, asset_server);
         }
@@ -488,7 +508,7 @@ fn character_movement(
     mut commands: Commands,
     asset_server: Res<SceneAsset>, //TODO make functions not need to call this
 
-    mut entity_properties: Query<(&mut ExternalImpulse, &mut Velocity, &mut Transform, &mut Scorch)>,
+    mut entity_properties: Query<(&mut ExternalImpulse, &mut Velocity, &mut Transform, &mut Scorch, Has<Submerged>)>,
     key_presses: Res<ButtonInput<KeyCode>>,
 
     time: Res<Time>,
@@ -498,7 +518,32 @@ fn character_movement(
     //I dont want to waste resources checking if it should move unless one of the keys are being pressed
     if key_presses.any_pressed([KeyCode::KeyW, KeyCode::KeyA, KeyCode::KeyS, KeyCode::KeyD, KeyCode::Space]) {
         // get the pos and vel of the Scorch
-        for (mut imp, mut velo , pos, mut scorch)in entity_properties.iter_mut() {
+        for (mut imp, mut velo , pos, mut scorch, swimming)in entity_properties.iter_mut() {
+            // in a fire pool WASD swims in any direction, there are no dashes or double jumps
+            if swimming {
+                // leaving the pool gives them back
+                scorch.grounded();
+                let mut dir = Vec2::ZERO;
+                if key_presses.pressed(KeyCode::KeyW) {
+                    dir.y += 1.0;
+                }
+                if key_presses.pressed(KeyCode::KeyS) {
+                    dir.y -= 1.0;
+                }
+                if key_presses.pressed(KeyCode::KeyA) {
+                    dir.x -= 1.0;
+                }
+                if key_presses.pressed(KeyCode::KeyD) {
+                    dir.x += 1.0;
+                }
+                velo.linvel += dir.normalize_or_zero() * SWIM_SPEED;
+                // jumping out at the surface
+                if key_presses.just_pressed(KeyCode::Space) {
+                    imp.impulse += Vec2::new(0.0, 30.0 * FORCE_STRENGTH);
+                }
+                continue;
+            }
+
             // this checks if theres an entity below the shpere within 2m
             //TODO after learning about queryfilters exclude embers, projectiles and enemies
             if let Some((_entity, _toi)) = &rc.cast_ray(
@@ -513,11 +558,6 @@ fn character_movement(
                 if key_presses.just_pressed(KeyCode::Space) {
                     imp.impulse += Vec2::new(0.0, 30.0 * FORCE_STRENGTH);
                 }
-                
-                // moveing up. use this when added swimming
-                // if key_presses.pressed(KeyCode::KeyW) {
-                //     velo.linvel += Vec2::new(0.0, 2.0);
-                // }
 
             } else {
                 //falling
@@ -624,16 +664,32 @@ fn character_movement(
     }
 }
 
+/// puts scorch at pos with full flame and nothing moving it
+pub fn respawn_scorch(
+    s_info: &mut Scorch,
+    s_impulse: &mut ExternalImpulse,
+    s_velocity: &mut Velocity,
+    s_position: &mut Transform,
+    pos: Vec2,
+) {
+    s_position.translation = pos.extend(-1.0);
+    s_impulse.impulse = Vec2::ZERO;
+    s_velocity.linvel = Vec2::ZERO;
+    s_info.reset();
+}
+
 // this applies flame regen and causes damage per second
+// when scorch runs out of flame they respawn at the last checkpoint
 fn per_frame_flame_change (
-    mut s_query: Query<&mut Scorch>,
+    mut s_query: Query<(&mut Scorch, &mut ExternalImpulse, &mut Velocity, &mut Transform)>,
+    respawn: Res<RespawnPoint>,
 ) {
-    if let Ok(mut s_info) = s_query.get_single_mut() {
+    if let Ok((mut s_info, mut s_impulse, mut s_velocity, mut s_position)) = s_query.get_single_mut() {
         if s_info.has_flame(s_info.get_dpf()) && !s_info.is_dead {
             s_info.apply_dpf();
         } else {
             println!("Scorch is dead");
-            s_info.is_dead = false;
+            respawn_scorch(&mut s_info, &mut s_impulse, &mut s_velocity, &mut s_position, respawn.pos);
         }
         s_info.regen_flame();
     }
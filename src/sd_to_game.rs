@@ -1,14 +1,25 @@
 use bevy::prelude::*;
+use bevy::asset::{io::Reader, AssetLoader, AssetLoadFailedEvent, AsyncReadExt, LoadContext};
 use bevy_rapier2d::prelude::*;
 use serde::Deserialize;
-use std::fs::File;
-use std::io::BufReader;
 use crate::asset_loader::SceneAsset;
 // external stuff
 // elsewhere in the project
-use crate::blocks::{BlockInfo, BlockTexture};
-use crate::enemies::{spawn_enemy, EnemyInfo};
+use crate::blocks::{BlockInfo, BlockShape};
+use crate::bounds::LevelBounds;
+use crate::camera::CameraLock;
+use crate::ember::EmberComponent;
+use crate::endless::{endless_bounds, EndlessRun};
+use crate::enemies::{spawn_enemy, EnemyInfo, ProjectileType};
+use crate::heat::Temperature;
+use crate::logic::spawn_mechanisms;
+use crate::level_format::{parse_json, parse_level, LevelData, LevelLoadError};
+use crate::pools::spawn_pool;
+use crate::scorch::{respawn_scorch, Scorch};
 use crate::state_system::AppState;
+use crate::tiled::TiledLoader;
+use crate::triggers::{spawn_trigger, Trigger};
+use crate::water::{spawn_water, WaterKind};
 
 #[derive(Bundle)]
 pub struct SdBundle {
@@ -20,93 +31,641 @@ pub struct SdPlugin;
 impl Plugin for SdPlugin {
     fn build(&self, app: &mut App) {
         app
+            .init_asset::<LevelData>()
+            .init_asset_loader::<LevelLoader>()
+            .init_asset_loader::<TiledLoader>()
+            .init_resource::<CurrentLevel>()
+            .init_resource::<RespawnPoint>()
+            .add_event::<LevelComplete>()
+            .add_event::<LevelReset>()
+            .add_systems(Startup, load_level_registry)
             //.add_systems(Startup, spawn_from_json)
             .add_systems(
-                OnEnter(AppState::InGame), 
-                spawn_from_json
+                OnEnter(AppState::InGame),
+                request_level
+            )
+            .add_systems(
+                Update,
+                (
+                    level_load_failed,
+                    spawn_from_json,
+                    hot_reload_level,
+                    reset_level,
+                    checkpoint_system,
+                    level_goal_system,
+                    advance_level,
+                ).chain().run_if(in_state(AppState::InGame))
             )
         ;
     }
 }
 
+/// where the ordered list of levels is kept, the level paths in it are relative to the assets folder
+const LEVEL_MANIFEST: &str = "assets/levels/levels.json";
+
 #[derive(Deserialize)]
-struct LevelData {
-    blocks: Vec<BlockData>,
-    enemies: Vec<EnemyData>,
+struct LevelManifest {
+    levels: Vec<LevelEntry>,
 }
 
-#[derive(Deserialize)]
-struct BlockData {
-    pos: [f32; 2],
-    size: [f32; 2],
-    block_info: BlockInfo,
+/// one level in the manifest, the order in the manifest is the order they are played in
+#[derive(Deserialize, Debug, Clone)]
+#[allow(dead_code)]
+pub struct LevelEntry {
+    /// short name used to refer to the level
+    pub id: String,
+    /// the name shown to the player
+    pub name: String,
+    /// path to the level json or Tiled map (.tmj), relative to the assets folder
+    pub path: String,
 }
 
-#[derive(Deserialize)]
-struct EnemyData {
-    pos: [f32; 2],
-    size: f32,
-    e_info: EnemyInfo,
+/// all the levels in the game in the order they are played
+#[derive(Resource, Debug, Default)]
+pub struct LevelRegistry {
+    pub levels: Vec<LevelEntry>,
+}
+
+impl LevelRegistry {
+    pub fn get(&self, index: usize) -> Option<&LevelEntry> {
+        self.levels.get(index)
+    }
+}
+
+/// the index into the LevelRegistry of the level being played
+#[derive(Resource, Debug, Default)]
+pub struct CurrentLevel {
+    pub index: usize,
+    /// the level file, kept so the asset stays loaded and can be hot reloaded
+    pub handle: Handle<LevelData>,
+    /// if the level has been spawned yet, the asset might still be loading
+    pub spawned: bool,
+}
+
+/// while this exists the level being played came from the editor, not the level files
+#[derive(Resource, Debug)]
+pub struct Playtest;
+
+/// sent when scorch finishes a level, the next level in the registry gets loaded
+#[derive(Event, Debug)]
+pub struct LevelComplete;
+
+/// sent after the level has been put back to how it is in the level file
+#[derive(Event, Debug)]
+pub struct LevelReset;
+
+/// the id of a block from the level file, so triggers can find it
+#[derive(Component, Debug, Clone)]
+pub struct LevelId(pub String);
+
+/// everything spawned from a level file has this, so it can be cleaned up when the level changes
+#[derive(Component, Debug, Clone, Copy)]
+pub struct LevelEntity;
+
+/// everything that should go when a level is cleared.
+/// summoned enemies and projectiles are not from the level file but should still go
+pub type LevelQuery<'w, 's> = Query<'w, 's, Entity, Or<(With<LevelEntity>, With<EnemyInfo>, With<ProjectileType>)>>;
+
+/// the area scorch has to reach to finish the level
+#[derive(Component, Debug, Clone, Copy)]
+pub struct LevelGoal {
+    /// half the width and height of the goal
+    pub size: Vec2,
+}
+
+/// where scorch respawns, the spawn of the level or the last checkpoint touched
+#[derive(Resource, Debug, Default)]
+pub struct RespawnPoint {
+    pub pos: Vec2,
+}
+
+/// touching this moves the RespawnPoint to it
+#[derive(Component, Debug, Clone, Copy)]
+pub struct Checkpoint {
+    /// half the width and height of the checkpoint
+    pub size: Vec2,
+    /// if scorch has touched it yet
+    pub reached: bool,
+}
+
+/// how checkpoints look before and after they are reached
+const CHECKPOINT_COLOR: Color = Color::srgba(1.0, 0.5, 0.0, 0.3);
+const CHECKPOINT_REACHED_COLOR: Color = Color::srgba(1.0, 0.5, 0.0, 0.7);
+
+/// reads the level manifest from disk
+pub fn read_level_manifest() -> Result<Vec<LevelEntry>, LevelLoadError> {
+    let bytes = std::fs::read(LEVEL_MANIFEST).map_err(|err| LevelLoadError::Io {
+        path: LEVEL_MANIFEST.to_string(),
+        err,
+    })?;
+    let manifest: LevelManifest = parse_json(LEVEL_MANIFEST, &bytes)?;
+    Ok(manifest.levels)
 }
 
-#[allow(dead_code, unreachable_patterns)]
-fn spawn_from_json(
+fn load_level_registry(
     mut commands: Commands,
+) {
+    match read_level_manifest() {
+        Ok(levels) => commands.insert_resource(LevelRegistry { levels }),
+        Err(err) => {
+            // the error gets shown when the player tries to start a level
+            println!("ERROR: {}", err);
+            commands.insert_resource(LevelRegistry::default());
+            commands.insert_resource(LevelLoadFailure { message: err.to_string() });
+        }
+    }
+}
+
+/// the message of the last level that failed to load, shown in AppState::LevelError
+#[derive(Resource, Debug, Default)]
+pub struct LevelLoadFailure {
+    pub message: String,
+}
+
+/// turns level json files into LevelData for the AssetServer
+#[derive(Default)]
+pub struct LevelLoader;
+
+impl AssetLoader for LevelLoader {
+    type Asset = LevelData;
+    type Settings = ();
+    type Error = LevelLoadError;
+
+    async fn load<'a>(
+        &'a self,
+        reader: &'a mut Reader<'_>,
+        _settings: &'a (),
+        load_context: &'a mut LoadContext<'_>,
+    ) -> Result<Self::Asset, Self::Error> {
+        let path = load_context.path().display().to_string();
+        let mut bytes = Vec::new();
+        reader
+            .read_to_end(&mut bytes)
+            .await
+            .map_err(|err| LevelLoadError::Io { path: path.clone(), err })?;
+        parse_level(&path, &bytes)
+    }
+
+    fn extensions(&self) -> &[&str] {
+        &["json"]
+    }
+}
+
+/// starts loading the current level, it gets spawned by spawn_from_json when it is ready
+fn request_level(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    registry: Res<LevelRegistry>,
+    mut current: ResMut<CurrentLevel>,
+    failure: Option<Res<LevelLoadFailure>>,
+    playtest: Option<Res<Playtest>>,
+    endless: Option<Res<EndlessRun>>,
+    mut respawn: ResMut<RespawnPoint>,
+    mut next_state: ResMut<NextState<AppState>>,
+) {
+    // the editor already set up the handle for the level
+    if playtest.is_some() {
+        current.spawned = false;
+        return;
+    }
+    // endless mode makes its own blocks, there is no level file
+    if endless.is_some() {
+        current.handle = Handle::default();
+        current.spawned = true;
+        respawn.pos = Vec2::ZERO;
+        commands.insert_resource(endless_bounds());
+        return;
+    }
+    if let Some(entry) = registry.get(current.index) {
+        println!("Loading level {}: {}", entry.id, entry.name);
+        current.handle = asset_server.load(entry.path.clone());
+        current.spawned = false;
+    } else {
+        // if the manifest failed to load keep that error, its more useful
+        if failure.is_none() {
+            println!("ERROR: no level at index {}", current.index);
+            commands.insert_resource(LevelLoadFailure {
+                message: format!("no level at index {} in {}", current.index, LEVEL_MANIFEST),
+            });
+        }
+        next_state.set(AppState::LevelError);
+    }
+}
+
+/// if the current level fails to load (or fails to hot reload), go to the error screen instead of crashing
+fn level_load_failed(
+    mut commands: Commands,
+    mut failed_events: EventReader<AssetLoadFailedEvent<LevelData>>,
+    current: Res<CurrentLevel>,
+    mut next_state: ResMut<NextState<AppState>>,
+) {
+    for event in failed_events.read() {
+        if event.id == current.handle.id() {
+            println!("ERROR: {}", event.error);
+            commands.insert_resource(LevelLoadFailure { message: event.error.to_string() });
+            next_state.set(AppState::LevelError);
+        }
+    }
+}
+
+/// spawns the current level once the AssetServer has finished loading it, then moves scorch to its spawn
+pub fn spawn_from_json(
+    mut commands: Commands,
+    asset_server: Res<SceneAsset>,
+    levels: Res<Assets<LevelData>>,
+    mut current: ResMut<CurrentLevel>,
+    mut respawn: ResMut<RespawnPoint>,
+    mut s_query: Query<(&mut Scorch, &mut ExternalImpulse, &mut Velocity, &mut Transform)>,
+) {
+    if current.spawned {
+        return;
+    }
+    if let Some(data) = levels.get(&current.handle) {
+        spawn_level(&mut commands, data, &asset_server);
+        respawn.pos = level_spawn(data);
+        if let Ok((mut s_info, mut s_impulse, mut s_velocity, mut s_position)) = s_query.get_single_mut() {
+            respawn_scorch(&mut s_info, &mut s_impulse, &mut s_velocity, &mut s_position, respawn.pos);
+        }
+        current.spawned = true;
+    }
+}
+
+/// where scorch starts in a level
+fn level_spawn(data: &LevelData) -> Vec2 {
+    data.spawn.map(Vec2::from).unwrap_or(Vec2::ZERO)
+}
+
+/// when the level file is edited while the game is running, respawn the level in place
+fn hot_reload_level(
+    mut commands: Commands,
+    mut asset_events: EventReader<AssetEvent<LevelData>>,
     asset_server: Res<SceneAsset>,
+    levels: Res<Assets<LevelData>>,
+    current: Res<CurrentLevel>,
+    mut respawn: ResMut<RespawnPoint>,
+
+    level_query: LevelQuery,
 ) {
-    // Open the JSON file
-    let file = File::open("levels/lv1.json").expect("Cannot open lv1.json");
-    let reader = BufReader::new(file);
+    for event in asset_events.read() {
+        if let AssetEvent::Modified { id } = event {
+            if *id != current.handle.id() || !current.spawned {
+                continue;
+            }
+            if let Some(data) = levels.get(*id) {
+                println!("Level file changed, respawning level");
+                clear_level(&mut commands, &level_query);
+                spawn_level(&mut commands, data, &asset_server);
+                // the checkpoints were respawned so none of them are reached anymore
+                respawn.pos = level_spawn(data);
+            }
+        }
+    }
+}
 
-    // Deserialize the JSON into a Vec<BlockData>
-    let data: LevelData = serde_json::from_reader(reader).expect("Error parsing lv1.json");    
+/// shift+R puts every block and enemy back how the level file has them, clears the embers
+/// and projectiles, and puts a fresh scorch at the spawn. R on its own only resets scorch
+fn reset_level(
+    mut commands: Commands,
+    key_presses: Res<ButtonInput<KeyCode>>,
+    asset_server: Res<SceneAsset>,
+    levels: Res<Assets<LevelData>>,
+    current: Res<CurrentLevel>,
+    mut respawn: ResMut<RespawnPoint>,
+    mut lock: ResMut<CameraLock>,
+    mut reset_events: EventWriter<LevelReset>,
 
-    for enemy in data.enemies {
-        spawn_enemy(
-            &mut commands, 
+    level_query: LevelQuery,
+    ember_query: Query<Entity, With<EmberComponent>>,
+    mut s_query: Query<(&mut Scorch, &mut ExternalImpulse, &mut Velocity, &mut Transform)>,
+) {
+    if 
+        !key_presses.just_pressed(KeyCode::KeyR) 
+        || !key_presses.any_pressed([KeyCode::ShiftLeft, KeyCode::ShiftRight]) 
+    {
+        return;
+    }
+    // still loading, there is nothing to reset yet
+    if !current.spawned {
+        return;
+    }
+    let Some(data) = levels.get(&current.handle) else {
+        return;
+    };
+
+    println!("Resetting level");
+    clear_level(&mut commands, &level_query);
+    for entity in ember_query.iter() {
+        commands.entity(entity).despawn();
+    }
+    spawn_level(&mut commands, data, &asset_server);
+    // the triggers that locked it were respawned
+    lock.pos = None;
+
+    // checkpoints were respawned too, so scorch goes back to the start
+    respawn.pos = level_spawn(data);
+    if let Ok((mut s_info, mut s_impulse, mut s_velocity, mut s_position)) = s_query.get_single_mut() {
+        // a fresh scorch so the dash and damage timers are reset too
+        *s_info = Scorch::default();
+        respawn_scorch(&mut s_info, &mut s_impulse, &mut s_velocity, &mut s_position, respawn.pos);
+    }
+    reset_events.send(LevelReset);
+}
+
+/// despawns everything from the current level
+pub fn clear_level(
+    commands: &mut Commands,
+    level_query: &LevelQuery,
+) {
+    for entity in level_query.iter() {
+        commands.entity(entity).despawn_recursive();
+    }
+}
+
+/// spawns everything in the level data
+fn spawn_level(
+    commands: &mut Commands,
+    data: &LevelData,
+    asset_server: &Res<SceneAsset>,
+) {
+    for enemy in &data.enemies {
+        let e_ent = spawn_enemy(
+            commands,
             Vec2::from(enemy.pos),
-            enemy.e_info,
+            enemy.e_info.to_enemy_info(enemy.size),
             enemy.size,
-            &asset_server,
+            asset_server,
         );
+        commands.entity(e_ent).insert(LevelEntity);
+    }
+
+    for block in &data.blocks {
+        let b_ent = spawn_block(
+            commands,
+            Vec2::from(block.pos),
+            Vec2::from(block.size),
+            block.block_info.to_block_info(&asset_server.materials),
+            &block.shape,
+            asset_server,
+        );
+        if let Some(id) = &block.id {
+            commands.entity(b_ent).insert(LevelId(id.clone()));
+        }
+    }
+
+    if let Some(goal) = &data.goal {
+        spawn_goal(commands, Vec2::from(goal.pos), Vec2::from(goal.size), asset_server);
+    }
+
+    for checkpoint in &data.checkpoints {
+        spawn_checkpoint(
+            commands,
+            Vec2::from(checkpoint.pos),
+            Vec2::from(checkpoint.size),
+            false,
+            asset_server,
+        );
+    }
+
+    for trigger in &data.triggers {
+        spawn_trigger(
+            commands,
+            Vec2::from(trigger.pos),
+            Vec2::from(trigger.size),
+            Trigger {
+                actions: trigger.actions.clone(),
+                once: trigger.once,
+                fired: false,
+            },
+        );
+    }
+
+    spawn_mechanisms(commands, data, asset_server);
+
+    for pool in &data.pools {
+        spawn_pool(commands, Vec2::from(pool.pos), Vec2::from(pool.size), asset_server);
+    }
+    for water in &data.water {
+        spawn_water(commands, Vec2::from(water.pos), Vec2::from(water.size), WaterKind::Water, asset_server);
+    }
+    for rain in &data.rain {
+        spawn_water(commands, Vec2::from(rain.pos), Vec2::from(rain.size), WaterKind::Rain, asset_server);
+    }
+
+    commands.insert_resource(LevelBounds::from_level(data));
+}
+
+/// spawns a checkpoint, size is half the width and height
+pub fn spawn_checkpoint(
+    commands: &mut Commands,
+    pos: Vec2,
+    size: Vec2,
+    reached: bool,
+    asset_server: &Res<SceneAsset>,
+) -> Entity {
+    commands
+        .spawn((
+            SpriteBundle {
+                texture: asset_server.t_temp.clone(),
+                transform: Transform::from_xyz(pos.x, pos.y, -2.0),
+                sprite: Sprite {
+                    custom_size: Some(size * 2.0),
+                    color: if reached { CHECKPOINT_REACHED_COLOR } else { CHECKPOINT_COLOR },
+                    ..default()
+                },
+                ..Default::default()
+            },
+            Checkpoint { size, reached },
+            LevelEntity,
+            StateScoped(AppState::InGame),
+        ))
+        .id()
+}
+
+/// spawns the goal of a level, size is half the width and height
+pub fn spawn_goal(
+    commands: &mut Commands,
+    pos: Vec2,
+    size: Vec2,
+    asset_server: &Res<SceneAsset>,
+) -> Entity {
+    commands
+        .spawn((
+            SpriteBundle {
+                texture: asset_server.t_temp.clone(),
+                transform: Transform::from_xyz(pos.x, pos.y, -2.0),
+                sprite: Sprite {
+                    custom_size: Some(size * 2.0),
+                    color: Color::srgba(1.0, 1.0, 1.0, 0.5),
+                    ..default()
+                },
+                ..Default::default()
+            },
+            LevelGoal { size },
+            LevelEntity,
+            StateScoped(AppState::InGame),
+        ))
+        .id()
+}
+
+/// the sprite for a block material, the placeholder if the material is not in the materials file
+pub fn block_texture(
+    material: &str,
+    asset_server: &Res<SceneAsset>,
+) -> Handle<Image> {
+    if asset_server.materials.contains(material) {
+        asset_server.materials.texture(material)
+    } else {
+        asset_server.t_temp.clone()
     }
+}
 
-    for block in data.blocks {
-        // load the texture based on what block it is
-        let b_texture = match block.block_info.texture {
-            BlockTexture::Stone =>  asset_server.t_block_unburnable.clone(),
-            BlockTexture::Wood =>   asset_server.t_block.clone(),
-            BlockTexture::Paper =>  asset_server.t_block_insta_burn.clone(),
-            _ =>                    asset_server.t_temp.clone(),
-        };
-
-        commands
-            .spawn((
-                SpriteBundle {
-                    texture: b_texture,
-                    transform: Transform {
-                        translation: Vec3::new(block.pos[0], block.pos[1], -1.0),
-                        scale: Vec3::new(
-                            block.size[0] / 100.0, 
-                            block.size[1] / 100.0,
-                            1.0
-                        ),
-                        ..Default::default()
-                    },
-                    sprite: Sprite {
-                        custom_size: Some(Vec2::new(200.0, 200.0)),
-                        ..default()
-                    },
+/// spawns a block centered on pos, size is half the width and height
+pub fn spawn_block(
+    commands: &mut Commands,
+    pos: Vec2,
+    size: Vec2,
+    block_info: BlockInfo,
+    shape: &BlockShape,
+    asset_server: &Res<SceneAsset>,
+) -> Entity {
+    let material = asset_server.materials.get(&block_info.material).clone();
+    let movable = block_info.movable;
+    let b_ent = commands
+        .spawn((
+            SpriteBundle {
+                // load the texture based on what block it is
+                texture: block_texture(&block_info.material, asset_server),
+                transform: Transform {
+                    translation: Vec3::new(pos.x, pos.y, -1.0),
+                    scale: Vec3::new(
+                        size.x / 100.0,
+                        size.y / 100.0,
+                        1.0
+                    ),
                     ..Default::default()
                 },
-                Collider::cuboid(100.0, 100.0),
-                block.block_info,
-                CollisionGroups::new(
-                    // G1 is Scorch, G2 is embers, G3 is blocks, G4 is enemies, G5 is enemy_projectiles
-                    Group::GROUP_3,
-                    Group::GROUP_1 | Group::GROUP_2 | Group::GROUP_4 | Group::GROUP_5,
-                ),
-                ActiveEvents::COLLISION_EVENTS,
-            ));
-    }
-}
\ No newline at end of file
+                sprite: Sprite {
+                    custom_size: Some(Vec2::new(200.0, 200.0)),
+                    color: material.color(),
+                    ..default()
+                },
+                ..Default::default()
+            },
+            shape.collider(),
+            shape.clone(),
+            Friction::coefficient(material.friction),
+            Restitution::coefficient(material.restitution),
+            block_info,
+            Temperature::default(),
+            CollisionGroups::new(
+                // G1 is Scorch, G2 is embers, G3 is blocks, G4 is enemies, G5 is enemy_projectiles, G6 is triggers, G7 is water, G8 is debris
+                // blocks are in their own filter so movable ones land on the rest
+                Group::GROUP_3,
+                Group::GROUP_1 | Group::GROUP_2 | Group::GROUP_3 | Group::GROUP_4 | Group::GROUP_5 | Group::GROUP_7 | Group::GROUP_8,
+            ),
+            ActiveEvents::COLLISION_EVENTS,
+            LevelEntity,
+            StateScoped(AppState::InGame),
+        ))
+        .id();
+
+    // without a RigidBody the collider is fixed, movable ones get their mass from their size and material
+    if movable {
+        commands.entity(b_ent).insert((
+            RigidBody::Dynamic,
+            ColliderMassProperties::Density(material.density),
+            Velocity::default(),
+            ExternalImpulse::default(),
+        ));
+    }
+    b_ent
+}
+
+/// when scorch touches a checkpoint it becomes where scorch respawns
+fn checkpoint_system(
+    s_query: Query<&Transform, With<Scorch>>,
+    mut checkpoint_query: Query<(&Transform, &mut Checkpoint, &mut Sprite)>,
+    mut respawn: ResMut<RespawnPoint>,
+) {
+    if let Ok(s_trans) = s_query.get_single() {
+        let s_pos = s_trans.translation.truncate();
+        for (c_trans, mut checkpoint, mut sprite) in checkpoint_query.iter_mut() {
+            if checkpoint.reached {
+                continue;
+            }
+            let offset = (s_pos - c_trans.translation.truncate()).abs();
+            if offset.x <= checkpoint.size.x && offset.y <= checkpoint.size.y {
+                println!("Checkpoint reached");
+                checkpoint.reached = true;
+                sprite.color = CHECKPOINT_REACHED_COLOR;
+                respawn.pos = c_trans.translation.truncate();
+            }
+        }
+    }
+}
+
+/// when scorch is inside the goal of the level the level is complete
+fn level_goal_system(
+    s_query: Query<&Transform, With<Scorch>>,
+    goal_query: Query<(&Transform, &LevelGoal)>,
+    mut complete_events: EventWriter<LevelComplete>,
+) {
+    if let Ok(s_trans) = s_query.get_single() {
+        let s_pos = s_trans.translation.truncate();
+        for (g_trans, goal) in goal_query.iter() {
+            let offset = (s_pos - g_trans.translation.truncate()).abs();
+            if offset.x <= goal.size.x && offset.y <= goal.size.y {
+                complete_events.send(LevelComplete);
+            }
+        }
+    }
+}
+
+/// clears out the current level and starts loading the next one in the registry
+fn advance_level(
+    mut commands: Commands,
+    mut complete_events: EventReader<LevelComplete>,
+    registry: Res<LevelRegistry>,
+    mut current: ResMut<CurrentLevel>,
+    asset_server: Res<AssetServer>,
+
+    level_query: LevelQuery,
+    goal_query: Query<Entity, With<LevelGoal>>,
+    mut s_query: Query<(&mut Scorch, &mut ExternalImpulse, &mut Velocity, &mut Transform)>,
+    playtest: Option<Res<Playtest>>,
+    mut lock: ResMut<CameraLock>,
+    mut next_state: ResMut<NextState<AppState>>,
+) {
+    // several goal events can come in on the same frame, only advance once
+    if complete_events.read().count() == 0 {
+        return;
+    }
+
+    // finishing a playtest goes back to the editor
+    if playtest.is_some() {
+        next_state.set(AppState::Editor);
+        return;
+    }
+
+    let Some(next) = registry.get(current.index + 1) else {
+        println!("All levels complete!");
+        // remove the goal so this only happens once
+        for entity in goal_query.iter() {
+            commands.entity(entity).despawn_recursive();
+        }
+        return;
+    };
+
+    clear_level(&mut commands, &level_query);
+    lock.pos = None;
+
+    println!("Loading level {}: {}", next.id, next.name);
+    current.index += 1;
+    current.handle = asset_server.load(next.path.clone());
+    current.spawned = false;
+
+    // put scorch back at the origin until the new level is loaded and says where its spawn is
+    if let Ok((mut s_info, mut s_impulse, mut s_velocity, mut s_position)) = s_query.get_single_mut() {
+        respawn_scorch(&mut s_info, &mut s_impulse, &mut s_velocity, &mut s_position, Vec2::ZERO);
+    }
+}
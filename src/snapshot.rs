@@ -0,0 +1,699 @@
+use bevy::prelude::*;
+use bevy::ecs::system::SystemParam;
+use bevy_rapier2d::prelude::*;
+use serde::{Deserialize, Serialize};
+
+// elsewhere in the project
+use crate::{
+    asset_loader::SceneAsset,
+    blocks::{BlockInfo, BlockShape, Heating},
+    bounds::LevelBounds,
+    burning::{spawn_charred, spawn_debris, Charred, Debris},
+    camera::CameraLock,
+    ember::EmberComponent,
+    endless::EndlessRun,
+    enemies::{ranged_enemy_shoot, spawn_enemy, EnemyInfo, ProjectileType},
+    heat::Temperature,
+    level_format::{parse_json, BoundsData, LevelLoadError, PoolData, WaterData},
+    logic::{
+        spawn_door, spawn_logic_gate, spawn_platform, spawn_spawner, spawn_switch, Door, LogicGate,
+        Platform, Spawner, Switch,
+    },
+    pools::{spawn_pool, FirePool},
+    scorch::Scorch,
+    sd_to_game::{
+        clear_level, spawn_block, spawn_checkpoint, spawn_from_json, spawn_goal, Checkpoint,
+        CurrentLevel, LevelEntity, LevelGoal, LevelId, LevelQuery, LevelRegistry, Playtest, RespawnPoint,
+    },
+    state_system::AppState,
+    triggers::{spawn_trigger, Trigger},
+    water::{spawn_water, Water, WaterKind, Wet},
+};
+
+// A snapshot is everything needed to pick a run back up: which level it is, scorch,
+// every block (with how far along its burn is), every enemy, every projectile in flight
+// the checkpoints that have been reached, the triggers that have gone off, the switches and the charred remains and debris.
+// How hot everything is, what is wet and what is being heated by a fire are kept so fires pick up where they were.
+// The bounds, pools, water and rain are kept too, since they come from the level file.
+// Embers are not kept, they only last a moment, the ones already flying are gone after loading.
+// The level file is not used when restoring, so blocks that already burned away stay gone.
+// F5 saves, F9 loads, and L on the main menu loads straight into the saved run.
+
+#[derive(Bundle)]
+pub struct SnapshotBundle {
+    // unused
+}
+
+pub struct SnapshotPlugin;
+
+impl Plugin for SnapshotPlugin {
+    fn build(&self, app: &mut App) {
+        app
+            .add_systems(
+                Update,
+                (save_snapshot_input, load_snapshot_input).run_if(in_state(AppState::InGame))
+            )
+            .add_systems(
+                Update,
+                (load_snapshot_input).run_if(in_state(AppState::MainMenu))
+            )
+            .add_systems(
+                Update,
+                (restore_snapshot)
+                    .before(spawn_from_json)
+                    .run_if(in_state(AppState::InGame).and_then(resource_exists::<PendingSnapshot>))
+            )
+        ;
+    }
+}
+
+/// where the snapshot is written, outside of assets so the file watcher does not pick it up
+const SAVE_FILE: &str = "saves/snapshot.json";
+
+/// bump this when the snapshot format changes, old saves are refused instead of half loaded
+const SNAPSHOT_VERSION: u32 = 3;
+
+/// just the version of a save, the rest of the fields are ignored
+#[derive(Deserialize)]
+struct SnapshotVersion {
+    version: u32,
+}
+
+#[derive(Deserialize, Serialize)]
+pub struct WorldSnapshot {
+    pub version: u32,
+    /// index into the LevelRegistry
+    pub level_index: usize,
+    /// the elapsed time when it was saved, all the times in the components are relative to this
+    pub saved_at: f32,
+    pub scorch: Option<ScorchSnapshot>,
+    pub blocks: Vec<BlockSnapshot>,
+    pub enemies: Vec<EnemySnapshot>,
+    pub projectiles: Vec<ProjectileSnapshot>,
+    pub goals: Vec<GoalSnapshot>,
+    pub respawn: [f32; 2],
+    pub checkpoints: Vec<CheckpointSnapshot>,
+    pub triggers: Vec<TriggerSnapshot>,
+    /// where the camera was locked by a trigger
+    pub camera_lock: Option<[f32; 2]>,
+    pub mechanisms: MechanismSnapshot,
+    pub bounds: Option<BoundsData>,
+    pub pools: Vec<PoolData>,
+    pub water: Vec<WaterData>,
+    pub rain: Vec<WaterData>,
+    /// what is left of the wooden blocks that burnt
+    pub charred: Vec<CharredSnapshot>,
+    /// the pieces of crumbled blocks that have not gone yet
+    pub debris: Vec<DebrisSnapshot>,
+}
+
+/// the switches, gates and what is wired to them
+#[derive(Deserialize, Serialize)]
+pub struct MechanismSnapshot {
+    pub switches: Vec<Switch>,
+    pub gates: Vec<LogicGate>,
+    pub doors: Vec<Door>,
+    pub platforms: Vec<PlatformSnapshot>,
+    pub spawners: Vec<Spawner>,
+}
+
+#[derive(Deserialize, Serialize)]
+pub struct PlatformSnapshot {
+    pub pos: [f32; 2],
+    pub platform: Platform,
+}
+
+#[derive(Deserialize, Serialize)]
+pub struct ScorchSnapshot {
+    pub pos: [f32; 2],
+    pub linvel: [f32; 2],
+    pub info: Scorch,
+    /// None starts it at its own heat
+    pub temperature: Option<f32>,
+    /// how long until it dries off, None if it was dry
+    pub wet: Option<f32>,
+}
+
+/// size is half the width and height, same as in the level files
+#[derive(Deserialize, Serialize)]
+pub struct BlockSnapshot {
+    /// the id from the level file, for triggers
+    pub id: Option<String>,
+    pub pos: [f32; 2],
+    pub size: [f32; 2],
+    pub info: BlockInfo,
+    pub shape: BlockShape,
+    /// only movable blocks turn or move, the rest are always 0
+    pub rotation: f32,
+    pub linvel: [f32; 2],
+    pub angvel: f32,
+    /// None starts it at the ambient temperature
+    pub temperature: Option<f32>,
+    /// when a fire next to it started heating it, see blocks::Heating
+    pub heating: Option<f32>,
+    /// how long until it dries off, None if it was dry
+    pub wet: Option<f32>,
+}
+
+/// the size is in the info
+#[derive(Deserialize, Serialize)]
+pub struct EnemySnapshot {
+    pub pos: [f32; 2],
+    pub linvel: [f32; 2],
+    /// enemies from the level file, summoned enemies are not
+    pub from_level: bool,
+    pub info: EnemyInfo,
+    /// None starts it at the ambient temperature
+    pub temperature: Option<f32>,
+    /// how long until it dries off, None if it was dry
+    pub wet: Option<f32>,
+}
+
+#[derive(Deserialize, Serialize)]
+pub struct ProjectileSnapshot {
+    pub pos: [f32; 2],
+    pub linvel: [f32; 2],
+    pub p_type: ProjectileType,
+}
+
+#[derive(Deserialize, Serialize)]
+pub struct GoalSnapshot {
+    pub pos: [f32; 2],
+    pub size: [f32; 2],
+}
+
+/// size is half the width and height
+#[derive(Deserialize, Serialize)]
+pub struct CharredSnapshot {
+    pub pos: [f32; 2],
+    pub size: [f32; 2],
+    pub material: String,
+    pub shape: BlockShape,
+}
+
+#[derive(Deserialize, Serialize)]
+pub struct DebrisSnapshot {
+    pub pos: [f32; 2],
+    pub linvel: [f32; 2],
+    pub material: String,
+    /// when it despawns
+    pub until: f32,
+}
+
+/// size is half the width and height
+#[derive(Deserialize, Serialize)]
+pub struct TriggerSnapshot {
+    pub pos: [f32; 2],
+    pub size: [f32; 2],
+    pub trigger: Trigger,
+}
+
+#[derive(Deserialize, Serialize)]
+pub struct CheckpointSnapshot {
+    pub pos: [f32; 2],
+    pub size: [f32; 2],
+    pub reached: bool,
+}
+
+/// a snapshot read from disk, waiting for InGame so it can be restored
+#[derive(Resource)]
+pub struct PendingSnapshot(pub WorldSnapshot);
+
+/// the queries for the areas in the level, bundled up since systems can only take so many params
+#[derive(SystemParam)]
+pub struct AreaQueries<'w, 's> {
+    goals: Query<'w, 's, (&'static LevelGoal, &'static Transform)>,
+    checkpoints: Query<'w, 's, (&'static Checkpoint, &'static Transform)>,
+    triggers: Query<'w, 's, (&'static Trigger, &'static Transform, &'static Collider)>,
+    pools: Query<'w, 's, (&'static FirePool, &'static Transform)>,
+    water: Query<'w, 's, (&'static Water, &'static Transform)>,
+    charred: Query<'w, 's, (&'static Charred, &'static Transform)>,
+    debris: Query<'w, 's, (&'static Debris, &'static Transform, &'static Velocity)>,
+}
+
+/// the queries for the logic entities, bundled up since systems can only take so many params
+#[derive(SystemParam)]
+pub struct MechanismQueries<'w, 's> {
+    switches: Query<'w, 's, &'static Switch>,
+    gates: Query<'w, 's, &'static LogicGate>,
+    doors: Query<'w, 's, &'static Door>,
+    platforms: Query<'w, 's, (&'static Platform, &'static Transform)>,
+    spawners: Query<'w, 's, &'static Spawner>,
+}
+
+/// the blocks with everything about them that is saved
+type BlockSaveQuery<'w, 's> = Query<'w, 's, (
+    &'static BlockInfo,
+    &'static BlockShape,
+    &'static Transform,
+    Option<&'static Velocity>,
+    Option<&'static LevelId>,
+    &'static Temperature,
+    Option<&'static Heating>,
+    Option<&'static Wet>,
+)>;
+
+/// the enemies with everything about them that is saved
+type EnemySaveQuery<'w, 's> = Query<'w, 's, (
+    &'static EnemyInfo,
+    &'static Transform,
+    &'static Velocity,
+    Has<LevelEntity>,
+    &'static Temperature,
+    Option<&'static Wet>,
+)>;
+
+/// F5 writes the world to the save file
+fn save_snapshot_input(
+    key_presses: Res<ButtonInput<KeyCode>>,
+    time: Res<Time>,
+    current: Res<CurrentLevel>,
+    playtest: Option<Res<Playtest>>,
+    endless: Option<Res<EndlessRun>>,
+    s_query: Query<(&Scorch, &Transform, &Velocity, &Temperature, Option<&Wet>)>,
+    b_query: BlockSaveQuery,
+    e_query: EnemySaveQuery,
+    p_query: Query<(&ProjectileType, &Transform, &Velocity)>,
+    areas: AreaQueries,
+    respawn: Res<RespawnPoint>,
+    lock: Res<CameraLock>,
+    bounds: Res<LevelBounds>,
+    mechanisms: MechanismQueries,
+) {
+    if !key_presses.just_pressed(KeyCode::F5) {
+        return;
+    }
+    // the level would be loaded from the level files, not the editor
+    if playtest.is_some() {
+        println!("can not save while playtesting");
+        return;
+    }
+    if endless.is_some() {
+        println!("can not save in endless mode");
+        return;
+    }
+    if !current.spawned {
+        println!("can not save while the level is loading");
+        return;
+    }
+
+    let snapshot = WorldSnapshot {
+        version: SNAPSHOT_VERSION,
+        level_index: current.index,
+        saved_at: time.elapsed_seconds(),
+        scorch: s_query.get_single().ok().map(|(info, trans, vel, temp, wet)| ScorchSnapshot {
+            pos: trans.translation.truncate().into(),
+            linvel: vel.linvel.into(),
+            info: *info,
+            temperature: Some(temp.degrees),
+            wet: wet.map(|wet| wet.drying),
+        }),
+        blocks: b_query
+            .iter()
+            .map(|(info, shape, trans, vel, id, temp, heating, wet)| BlockSnapshot {
+                id: id.map(|id| id.0.clone()),
+                pos: trans.translation.truncate().into(),
+                // blocks are a 200x200 sprite scaled to size
+                size: (trans.scale.truncate() * 100.0).into(),
+                info: info.clone(),
+                shape: shape.clone(),
+                rotation: trans.rotation.to_euler(EulerRot::ZYX).0,
+                linvel: vel.map_or(Vec2::ZERO, |vel| vel.linvel).into(),
+                angvel: vel.map_or(0.0, |vel| vel.angvel),
+                temperature: Some(temp.degrees),
+                heating: heating.map(|heating| heating.since),
+                wet: wet.map(|wet| wet.drying),
+            })
+            .collect(),
+        enemies: e_query
+            .iter()
+            .map(|(info, trans, vel, from_level, temp, wet)| EnemySnapshot {
+                pos: trans.translation.truncate().into(),
+                linvel: vel.linvel.into(),
+                from_level,
+                info: *info,
+                temperature: Some(temp.degrees),
+                wet: wet.map(|wet| wet.drying),
+            })
+            .collect(),
+        projectiles: p_query
+            .iter()
+            .map(|(p_type, trans, vel)| ProjectileSnapshot {
+                pos: trans.translation.truncate().into(),
+                linvel: vel.linvel.into(),
+                p_type: *p_type,
+            })
+            .collect(),
+        goals: areas
+            .goals
+            .iter()
+            .map(|(goal, trans)| GoalSnapshot {
+                pos: trans.translation.truncate().into(),
+                size: goal.size.into(),
+            })
+            .collect(),
+        respawn: respawn.pos.into(),
+        checkpoints: areas
+            .checkpoints
+            .iter()
+            .map(|(checkpoint, trans)| CheckpointSnapshot {
+                pos: trans.translation.truncate().into(),
+                size: checkpoint.size.into(),
+                reached: checkpoint.reached,
+            })
+            .collect(),
+        triggers: areas
+            .triggers
+            .iter()
+            .filter_map(|(trigger, trans, collider)| {
+                // triggers are always cuboids, the collider is the only place the size is kept
+                let size = collider.as_cuboid()?.half_extents();
+                Some(TriggerSnapshot {
+                    pos: trans.translation.truncate().into(),
+                    size: size.into(),
+                    trigger: trigger.clone(),
+                })
+            })
+            .collect(),
+        camera_lock: lock.pos.map(Vec2::into),
+        mechanisms: MechanismSnapshot {
+            switches: mechanisms.switches.iter().cloned().collect(),
+            gates: mechanisms.gates.iter().cloned().collect(),
+            doors: mechanisms.doors.iter().cloned().collect(),
+            platforms: mechanisms
+                .platforms
+                .iter()
+                .map(|(platform, trans)| PlatformSnapshot {
+                    pos: trans.translation.truncate().into(),
+                    platform: platform.clone(),
+                })
+                .collect(),
+            spawners: mechanisms.spawners.iter().cloned().collect(),
+        },
+        bounds: bounds.rect.map(|rect| BoundsData {
+            min: rect.min.into(),
+            max: rect.max.into(),
+        }),
+        pools: areas
+            .pools
+            .iter()
+            .map(|(pool, trans)| PoolData {
+                pos: trans.translation.truncate().into(),
+                size: pool.size.into(),
+            })
+            .collect(),
+        water: water_of_kind(&areas.water, WaterKind::Water),
+        rain: water_of_kind(&areas.water, WaterKind::Rain),
+        charred: areas
+            .charred
+            .iter()
+            .map(|(charred, trans)| CharredSnapshot {
+                pos: trans.translation.truncate().into(),
+                size: charred.size.into(),
+                material: charred.material.clone(),
+                shape: charred.shape.clone(),
+            })
+            .collect(),
+        debris: areas
+            .debris
+            .iter()
+            .map(|(debris, trans, vel)| DebrisSnapshot {
+                pos: trans.translation.truncate().into(),
+                linvel: vel.linvel.into(),
+                material: debris.material.clone(),
+                until: debris.until,
+            })
+            .collect(),
+    };
+
+    match write_snapshot(&snapshot) {
+        Ok(()) => println!("Saved to {}", SAVE_FILE),
+        Err(err) => println!("ERROR: could not save to {}: {}", SAVE_FILE, err),
+    }
+}
+
+fn water_of_kind(water_query: &Query<(&Water, &Transform)>, kind: WaterKind) -> Vec<WaterData> {
+    water_query
+        .iter()
+        .filter(|(water, _)| water.kind == kind)
+        .map(|(water, trans)| WaterData {
+            pos: trans.translation.truncate().into(),
+            size: water.size.into(),
+        })
+        .collect()
+}
+
+fn write_snapshot(snapshot: &WorldSnapshot) -> std::io::Result<()> {
+    if let Some(folder) = std::path::Path::new(SAVE_FILE).parent() {
+        std::fs::create_dir_all(folder)?;
+    }
+    let json = serde_json::to_string_pretty(snapshot)?;
+    std::fs::write(SAVE_FILE, json)
+}
+
+/// reads the save file, a snapshot from a different version is an error
+pub fn read_snapshot() -> Result<WorldSnapshot, LevelLoadError> {
+    let bytes = std::fs::read(SAVE_FILE).map_err(|err| LevelLoadError::Io {
+        path: SAVE_FILE.to_string(),
+        err,
+    })?;
+    // the version is checked first, an old save would fail on the fields it is missing instead
+    let SnapshotVersion { version } = parse_json(SAVE_FILE, &bytes)?;
+    if version != SNAPSHOT_VERSION {
+        return Err(LevelLoadError::Version {
+            path: SAVE_FILE.to_string(),
+            version: version as u64,
+            oldest: SNAPSHOT_VERSION,
+            newest: SNAPSHOT_VERSION,
+        });
+    }
+    parse_json(SAVE_FILE, &bytes)
+}
+
+/// F9 in game or L on the main menu loads the save file
+fn load_snapshot_input(
+    mut commands: Commands,
+    key_presses: Res<ButtonInput<KeyCode>>,
+    state: Res<State<AppState>>,
+    mut next_state: ResMut<NextState<AppState>>,
+) {
+    let key = match state.get() {
+        AppState::MainMenu => KeyCode::KeyL,
+        _ => KeyCode::F9,
+    };
+    if !key_presses.just_pressed(key) {
+        return;
+    }
+
+    match read_snapshot() {
+        Ok(snapshot) => {
+            commands.insert_resource(PendingSnapshot(snapshot));
+            if *state.get() != AppState::InGame {
+                next_state.set(AppState::InGame);
+            }
+        }
+        Err(err) => println!("ERROR: {}", err),
+    }
+}
+
+/// swaps the world for the one in the snapshot.
+/// runs before spawn_from_json so the level file does not get spawned on top of it
+fn restore_snapshot(
+    mut commands: Commands,
+    snapshot: Res<PendingSnapshot>,
+    time: Res<Time>,
+    asset_server: Res<AssetServer>,
+    scene_assets: Res<SceneAsset>,
+    registry: Res<LevelRegistry>,
+    mut current: ResMut<CurrentLevel>,
+    mut respawn: ResMut<RespawnPoint>,
+    mut lock: ResMut<CameraLock>,
+    level_query: LevelQuery,
+    ember_query: Query<Entity, With<EmberComponent>>,
+    mut s_query: Query<(Entity, &mut Scorch, &mut Transform, &mut Velocity, &mut ExternalImpulse, &mut Temperature)>,
+) {
+    let snapshot = &snapshot.0;
+    commands.remove_resource::<PendingSnapshot>();
+    // a save is always of the level files, even if it was loaded during a playtest or endless run
+    commands.remove_resource::<Playtest>();
+    commands.remove_resource::<EndlessRun>();
+
+    let Some(entry) = registry.get(snapshot.level_index) else {
+        println!("ERROR: the save is for level {}, which is not in the level list", snapshot.level_index);
+        return;
+    };
+
+    // the level handle is still needed for hot reloading and moving on to the next level
+    current.index = snapshot.level_index;
+    current.handle = asset_server.load(entry.path.clone());
+    current.spawned = true;
+
+    clear_level(&mut commands, &level_query);
+    // embers are not level entities, they would keep setting fire to the restored blocks
+    for entity in ember_query.iter() {
+        commands.entity(entity).despawn_recursive();
+    }
+
+    // the times in the components are from when the game was saved, move them to now
+    let shift = time.elapsed_seconds() - snapshot.saved_at;
+
+    if let Some(saved) = &snapshot.scorch {
+        if let Ok((s_ent, mut s_info, mut s_trans, mut s_vel, mut s_imp, mut s_temp)) = s_query.get_single_mut() {
+            let mut info = saved.info;
+            info.i_frame_timer = shift_time(info.i_frame_timer, shift);
+            info.dash.1 = shift_time(info.dash.1, shift);
+            info.a_dash = shift_time(info.a_dash, shift);
+            info.d_dash = shift_time(info.d_dash, shift);
+            *s_info = info;
+            s_trans.translation = Vec2::from(saved.pos).extend(s_trans.translation.z);
+            s_vel.linvel = Vec2::from(saved.linvel);
+            s_imp.impulse = Vec2::ZERO;
+            if let Some(degrees) = saved.temperature {
+                *s_temp = Temperature::new(degrees);
+            }
+            // scorch is not respawned so it could still be wet from before loading
+            match saved.wet {
+                Some(drying) => commands.entity(s_ent).insert(Wet { drying }),
+                None => commands.entity(s_ent).remove::<Wet>(),
+            };
+        }
+    }
+
+    for block in &snapshot.blocks {
+        // the burn state is how long is left, not a time, so it does not need shifting
+        let info = block.info.clone();
+        let b_ent = spawn_block(
+            &mut commands,
+            Vec2::from(block.pos),
+            Vec2::from(block.size),
+            info,
+            &block.shape,
+            &scene_assets,
+        );
+        if let Some(id) = &block.id {
+            commands.entity(b_ent).insert(LevelId(id.clone()));
+        }
+        if let Some(degrees) = block.temperature {
+            commands.entity(b_ent).insert(Temperature::new(degrees));
+        }
+        if let Some(since) = block.heating {
+            commands.entity(b_ent).insert(Heating { since: since + shift });
+        }
+        if let Some(drying) = block.wet {
+            commands.entity(b_ent).insert(Wet { drying });
+        }
+        if block.info.movable {
+            // spawn_block puts it upright and still
+            commands.entity(b_ent).insert((
+                Transform {
+                    translation: Vec2::from(block.pos).extend(-1.0),
+                    rotation: Quat::from_rotation_z(block.rotation),
+                    scale: (Vec2::from(block.size) / 100.0).extend(1.0),
+                },
+                Velocity {
+                    linvel: Vec2::from(block.linvel),
+                    angvel: block.angvel,
+                },
+            ));
+        }
+    }
+    for charred in &snapshot.charred {
+        let (pos, size) = (Vec2::from(charred.pos), Vec2::from(charred.size));
+        spawn_charred(&mut commands, pos, size, &charred.material, &charred.shape, &scene_assets);
+    }
+    for debris in &snapshot.debris {
+        let (pos, linvel) = (Vec2::from(debris.pos), Vec2::from(debris.linvel));
+        spawn_debris(&mut commands, pos, linvel, &debris.material, &scene_assets, debris.until + shift);
+    }
+
+    for enemy in &snapshot.enemies {
+        let mut info = enemy.info;
+        info.active_cooldown = shift_time(info.active_cooldown, shift);
+        info.stunned_until = shift_time(info.stunned_until, shift);
+        let e_ent = spawn_enemy(&mut commands, Vec2::from(enemy.pos), info, info.size, &scene_assets);
+        commands.entity(e_ent).insert(Velocity::linear(Vec2::from(enemy.linvel)));
+        if enemy.from_level {
+            commands.entity(e_ent).insert(LevelEntity);
+        }
+        if let Some(degrees) = enemy.temperature {
+            commands.entity(e_ent).insert(Temperature::new(degrees));
+        }
+        if let Some(drying) = enemy.wet {
+            commands.entity(e_ent).insert(Wet { drying });
+        }
+    }
+
+    for projectile in &snapshot.projectiles {
+        let p_ent = ranged_enemy_shoot(
+            &mut commands,
+            Vec2::from(projectile.pos),
+            Vec2::ZERO,
+            projectile.p_type,
+            &scene_assets,
+        );
+        commands.entity(p_ent).insert(Velocity::linear(Vec2::from(projectile.linvel)));
+    }
+
+    for goal in &snapshot.goals {
+        spawn_goal(&mut commands, Vec2::from(goal.pos), Vec2::from(goal.size), &scene_assets);
+    }
+
+    for checkpoint in &snapshot.checkpoints {
+        spawn_checkpoint(
+            &mut commands,
+            Vec2::from(checkpoint.pos),
+            Vec2::from(checkpoint.size),
+            checkpoint.reached,
+            &scene_assets,
+        );
+    }
+    respawn.pos = Vec2::from(snapshot.respawn);
+    commands.insert_resource(LevelBounds {
+        rect: snapshot.bounds.map(|bounds| bounds.to_rect()),
+    });
+
+    for pool in &snapshot.pools {
+        spawn_pool(&mut commands, Vec2::from(pool.pos), Vec2::from(pool.size), &scene_assets);
+    }
+    for water in &snapshot.water {
+        spawn_water(&mut commands, Vec2::from(water.pos), Vec2::from(water.size), WaterKind::Water, &scene_assets);
+    }
+    for rain in &snapshot.rain {
+        spawn_water(&mut commands, Vec2::from(rain.pos), Vec2::from(rain.size), WaterKind::Rain, &scene_assets);
+    }
+
+    for trigger in &snapshot.triggers {
+        spawn_trigger(&mut commands, Vec2::from(trigger.pos), Vec2::from(trigger.size), trigger.trigger.clone());
+    }
+    lock.pos = snapshot.camera_lock.map(Vec2::from);
+
+    let mechanisms = &snapshot.mechanisms;
+    for switch in &mechanisms.switches {
+        let mut switch = switch.clone();
+        switch.on_since = switch.on_since.map(|since| since + shift);
+        spawn_switch(&mut commands, switch);
+    }
+    for gate in &mechanisms.gates {
+        spawn_logic_gate(&mut commands, gate.clone());
+    }
+    for door in &mechanisms.doors {
+        spawn_door(&mut commands, door.clone(), &scene_assets);
+    }
+    for platform in &mechanisms.platforms {
+        spawn_platform(&mut commands, platform.platform.clone(), Vec2::from(platform.pos), &scene_assets);
+    }
+    for spawner in &mechanisms.spawners {
+        spawn_spawner(&mut commands, spawner.clone());
+    }
+
+    println!("Loaded {}", SAVE_FILE);
+}
+
+/// moves a saved time to the current clock, 0.0 means never and is left alone
+fn shift_time(saved: f32, shift: f32) -> f32 {
+    if saved == 0.0 {
+        0.0
+    } else {
+        saved + shift
+    }
+}
@@ -12,6 +12,8 @@ impl Plugin for StateSystemPlugin {
     fn build(&self, app: &mut App) {
         app
             .insert_state(AppState::LoadingScreen)
+            // entities with StateScoped get despawned when leaving that state
+            .enable_state_scoped_entities::<AppState>()
         ;
     }
 }
@@ -19,7 +21,11 @@ impl Plugin for StateSystemPlugin {
 #[derive(States, Debug, Clone, PartialEq, Eq, Hash)]
 pub enum AppState {
     LoadingScreen,
-    //MainMenu,
+    MainMenu,
     InGame,
+    /// a level failed to load, the error is shown and the player can retry
+    LevelError,
+    /// placing and editing the blocks and enemies of a level
+    Editor,
 }
 
@@ -0,0 +1,329 @@
+use bevy::prelude::*;
+use bevy::asset::{io::Reader, AssetLoader, AsyncReadExt, LoadContext};
+use serde::Deserialize;
+use serde_json::{json, Map, Value};
+
+// elsewhere in the project
+use crate::level_format::{level_from_value, parse_json, LevelData, LevelLoadError, LEVEL_VERSION};
+
+// Tiled maps (.tmj) can be used as levels instead of the level json.
+// Rectangles on the "blocks" layer become blocks, their custom properties are the block_info
+// (material, burnable, extinguishable, burn_time, spread_delay, movable, explosive) and an "id" property names the block for triggers.
+// Anything left out comes from the material, see materials.rs.
+// Polygons on the "blocks" layer become polygon blocks the same way, they have to be convex.
+// A rectangle named "goal" is the goal instead,
+// ones named "checkpoint" are checkpoints, and the center of an object named "spawn" is the spawn.
+// A rectangle named "bounds" is the edges of the level, ones named "pool" are fire pools
+// and ones named "water" or "rain" are water and rain.
+// Points on the "enemies" layer become enemies, their custom properties are the e_info,
+// and a "size" property sets the radius.
+// Tiled has y going down from the top left, the game has y going up so it is flipped.
+
+/// the object layer blocks are read from
+const BLOCK_LAYER: &str = "blocks";
+/// the object layer enemies are read from
+const ENEMY_LAYER: &str = "enemies";
+/// the radius of enemies that do not set one
+const DEFAULT_ENEMY_SIZE: f32 = 25.0;
+
+#[derive(Deserialize)]
+struct TiledMap {
+    #[serde(default)]
+    layers: Vec<TiledLayer>,
+}
+
+#[derive(Deserialize)]
+struct TiledLayer {
+    #[serde(default)]
+    name: String,
+    #[serde(default)]
+    objects: Vec<TiledObject>,
+    /// group layers have more layers inside of them
+    #[serde(default)]
+    layers: Vec<TiledLayer>,
+}
+
+#[derive(Deserialize)]
+struct TiledObject {
+    #[serde(default)]
+    name: String,
+    x: f32,
+    y: f32,
+    #[serde(default)]
+    width: f32,
+    #[serde(default)]
+    height: f32,
+    #[serde(default)]
+    properties: Vec<TiledProperty>,
+    /// only on polygon objects, relative to x and y
+    #[serde(default)]
+    polygon: Option<Vec<TiledPoint>>,
+}
+
+#[derive(Deserialize)]
+struct TiledPoint {
+    x: f32,
+    y: f32,
+}
+
+#[derive(Deserialize)]
+struct TiledProperty {
+    name: String,
+    value: Value,
+}
+
+impl TiledObject {
+    /// the center of the object in game coordinates
+    fn center(&self) -> [f32; 2] {
+        [self.x + self.width / 2.0, -(self.y + self.height / 2.0)]
+    }
+
+    fn half_size(&self) -> [f32; 2] {
+        [self.width / 2.0, self.height / 2.0]
+    }
+
+    /// the bottom left and top right corners, with y flipped
+    fn corners(&self) -> ([f32; 2], [f32; 2]) {
+        ([self.x, -(self.y + self.height)], [self.x + self.width, -self.y])
+    }
+
+    /// the center, half size and shape of a block, polygons get the box around them
+    /// and their points from -1.0 to 1.0 inside of it
+    fn block_shape(&self) -> ([f32; 2], [f32; 2], Value) {
+        let Some(points) = self.polygon.as_ref().filter(|points| !points.is_empty()) else {
+            return (self.center(), self.half_size(), Value::Null);
+        };
+        let (mut min, mut max) = (Vec2::splat(f32::INFINITY), Vec2::splat(f32::NEG_INFINITY));
+        for point in points {
+            min = min.min(Vec2::new(point.x, point.y));
+            max = max.max(Vec2::new(point.x, point.y));
+        }
+        let center = (min + max) / 2.0;
+        let half = ((max - min) / 2.0).max(Vec2::splat(f32::EPSILON));
+        let unit: Vec<[f32; 2]> = points
+            .iter()
+            .map(|point| [(point.x - center.x) / half.x, -(point.y - center.y) / half.y])
+            .collect();
+        (
+            [self.x + center.x, -(self.y + center.y)],
+            half.into(),
+            json!({ "type": "Polygon", "points": unit }),
+        )
+    }
+
+    fn property_map(&self) -> Map<String, Value> {
+        self.properties
+            .iter()
+            .map(|prop| (prop.name.clone(), prop.value.clone()))
+            .collect()
+    }
+}
+
+/// turns Tiled maps into LevelData for the AssetServer
+#[derive(Default)]
+pub struct TiledLoader;
+
+impl AssetLoader for TiledLoader {
+    type Asset = LevelData;
+    type Settings = ();
+    type Error = LevelLoadError;
+
+    async fn load<'a>(
+        &'a self,
+        reader: &'a mut Reader<'_>,
+        _settings: &'a (),
+        load_context: &'a mut LoadContext<'_>,
+    ) -> Result<Self::Asset, Self::Error> {
+        let path = load_context.path().display().to_string();
+        let mut bytes = Vec::new();
+        reader
+            .read_to_end(&mut bytes)
+            .await
+            .map_err(|err| LevelLoadError::Io { path: path.clone(), err })?;
+        parse_tiled_map(&path, &bytes)
+    }
+
+    fn extensions(&self) -> &[&str] {
+        &["tmj"]
+    }
+}
+
+/// parses a Tiled map and converts it into a level
+pub fn parse_tiled_map(path: &str, bytes: &[u8]) -> Result<LevelData, LevelLoadError> {
+    let map: TiledMap = parse_json(path, bytes)?;
+
+    let mut blocks = Vec::new();
+    let mut enemies = Vec::new();
+    let mut goal = Value::Null;
+    let mut spawn = Value::Null;
+    let mut bounds = Value::Null;
+    let mut pools = Vec::new();
+    let mut water = Vec::new();
+    let mut rain = Vec::new();
+    let mut checkpoints = Vec::new();
+    visit_layers(&map.layers, &mut |layer| {
+        match layer.name.as_str() {
+            BLOCK_LAYER => {
+                for object in &layer.objects {
+                    match object.name.as_str() {
+                        "goal" => {
+                            goal = json!({
+                                "pos": object.center(),
+                                "size": object.half_size(),
+                            });
+                        }
+                        "checkpoint" => checkpoints.push(json!({
+                            "pos": object.center(),
+                            "size": object.half_size(),
+                        })),
+                        "spawn" => spawn = json!(object.center()),
+                        "pool" => pools.push(json!({
+                            "pos": object.center(),
+                            "size": object.half_size(),
+                        })),
+                        "water" => water.push(json!({
+                            "pos": object.center(),
+                            "size": object.half_size(),
+                        })),
+                        "rain" => rain.push(json!({
+                            "pos": object.center(),
+                            "size": object.half_size(),
+                        })),
+                        "bounds" => {
+                            let (min, max) = object.corners();
+                            bounds = json!({ "min": min, "max": max });
+                        }
+                        _ => {
+                            let mut block_info = object.property_map();
+                            // the id is on the block not its info
+                            let id = block_info.remove("id").unwrap_or(Value::Null);
+                            let (pos, size, shape) = object.block_shape();
+                            let mut block = json!({
+                                "id": id,
+                                "pos": pos,
+                                "size": size,
+                                "block_info": block_info,
+                            });
+                            // rects leave it out, null is not a shape
+                            if !shape.is_null() {
+                                block["shape"] = shape;
+                            }
+                            blocks.push(block);
+                        }
+                    }
+                }
+            }
+            ENEMY_LAYER => {
+                for object in &layer.objects {
+                    let mut e_info = object.property_map();
+                    // the radius is on the enemy not its info, rectangles use their width
+                    let size = e_info
+                        .remove("size")
+                        .unwrap_or_else(|| {
+                            if object.width > 0.0 {
+                                Value::from(object.width / 2.0)
+                            } else {
+                                Value::from(DEFAULT_ENEMY_SIZE)
+                            }
+                        });
+                    enemies.push(json!({
+                        "pos": object.center(),
+                        "size": size,
+                        "e_info": e_info,
+                    }));
+                }
+            }
+            _ => {}
+        }
+    });
+
+    let level = json!({
+        "version": LEVEL_VERSION,
+        "blocks": blocks,
+        "enemies": enemies,
+        "goal": goal,
+        "spawn": spawn,
+        "checkpoints": checkpoints,
+        "bounds": bounds,
+        "pools": pools,
+        "water": water,
+        "rain": rain,
+    });
+    level_from_value(path, level)
+}
+
+/// calls visit on every layer, including the ones inside group layers
+fn visit_layers(layers: &[TiledLayer], visit: &mut impl FnMut(&TiledLayer)) {
+    for layer in layers {
+        visit(layer);
+        visit_layers(&layer.layers, visit);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::blocks::BlockShape;
+
+    #[test]
+    fn minimal_maps_are_imported() {
+        let text = r#"{
+    "layers": [
+        { "name": "blocks", "objects": [
+            { "name": "", "x": 0.0, "y": 100.0, "width": 200.0, "height": 50.0, "properties": [
+                { "name": "material", "type": "string", "value": "Wood" },
+                { "name": "burn_time", "type": "float", "value": 4.0 },
+                { "name": "id", "type": "string", "value": "plank" }
+            ] },
+            { "name": "goal", "x": 500.0, "y": 0.0, "width": 100.0, "height": 100.0 },
+            { "name": "spawn", "x": 10.0, "y": 10.0, "width": 20.0, "height": 20.0 },
+            { "name": "", "x": 300.0, "y": 100.0, "polygon": [
+                { "x": 0.0, "y": 0.0 }, { "x": 100.0, "y": 0.0 }, { "x": 0.0, "y": -100.0 }
+            ] }
+        ] },
+        { "name": "group", "layers": [
+            { "name": "enemies", "objects": [
+                { "name": "", "x": 50.0, "y": -200.0, "properties": [
+                    { "name": "e_type", "type": "string", "value": "RunDown" }
+                ] }
+            ] }
+        ] }
+    ]
+}"#;
+        let level = parse_tiled_map("test.tmj", text.as_bytes()).unwrap();
+        assert_eq!(level.version, LEVEL_VERSION);
+
+        // y is flipped and objects are placed by their top left corner
+        let block = &level.blocks[0];
+        assert_eq!(block.pos, [100.0, -125.0]);
+        assert_eq!(block.size, [100.0, 25.0]);
+        assert_eq!(block.id.as_deref(), Some("plank"));
+        assert_eq!(block.block_info.material, "Wood");
+        assert_eq!(block.block_info.burn_time, Some(4.0));
+        assert!(block.shape.is_rect());
+
+        let polygon = &level.blocks[1];
+        assert_eq!(polygon.pos, [350.0, -50.0]);
+        assert_eq!(polygon.size, [50.0, 50.0]);
+        assert_eq!(
+            polygon.shape,
+            BlockShape::Polygon { points: vec![[-1.0, -1.0], [1.0, -1.0], [-1.0, 1.0]] }
+        );
+
+        let goal = level.goal.as_ref().unwrap();
+        assert_eq!(goal.pos, [550.0, -50.0]);
+        assert_eq!(level.spawn, Some([20.0, -20.0]));
+
+        // enemies in group layers count too, points use the default size
+        assert_eq!(level.enemies.len(), 1);
+        assert_eq!(level.enemies[0].pos, [50.0, 200.0]);
+        assert_eq!(level.enemies[0].size, DEFAULT_ENEMY_SIZE);
+    }
+
+    #[test]
+    fn the_shipped_map_is_imported() {
+        let bytes = std::fs::read("assets/levels/lv3.tmj").unwrap();
+        let level = parse_tiled_map("lv3.tmj", &bytes).unwrap();
+        assert!(!level.blocks.is_empty());
+    }
+}
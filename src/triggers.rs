@@ -0,0 +1,190 @@
+use bevy::prelude::*;
+use bevy_rapier2d::prelude::*;
+use serde::{Deserialize, Serialize};
+
+// elsewhere in the project
+use crate::{
+    asset_loader::SceneAsset,
+    blocks::BlockInfo,
+    camera::CameraLock,
+    enemies::spawn_enemy,
+    heat::{Temperature, FLAME_TEMP},
+    level_format::TriggerAction,
+    scorch::Scorch,
+    sd_to_game::{LevelEntity, LevelId},
+    state_system::AppState,
+};
+
+// Triggers are sensors placed by the level, when scorch enters one its actions run.
+// They only collide with scorch, the actions are listed in level_format::TriggerAction.
+
+#[derive(Bundle)]
+pub struct TriggerBundle {
+    pub trigger: Trigger,
+}
+
+pub struct TriggerPlugin;
+
+impl Plugin for TriggerPlugin {
+    fn build(&self, app: &mut App) {
+        app
+            .add_systems(
+                Update,
+                (trigger_system, hint_timeout).run_if(in_state(AppState::InGame))
+            )
+            .add_systems(OnExit(AppState::InGame), unlock_camera)
+        ;
+    }
+}
+
+/// the actions of a trigger, the sensor collider is on the same entity
+#[derive(Component, Debug, Clone, Deserialize, Serialize)]
+pub struct Trigger {
+    pub actions: Vec<TriggerAction>,
+    /// only runs the first time scorch enters
+    pub once: bool,
+    /// if scorch has entered it yet
+    pub fired: bool,
+}
+
+/// the hint on screen, removed at the elapsed time in until
+#[derive(Component)]
+pub struct HintText {
+    until: f32,
+}
+
+/// spawns a trigger area, size is half the width and height
+pub fn spawn_trigger(
+    commands: &mut Commands,
+    pos: Vec2,
+    size: Vec2,
+    trigger: Trigger,
+) -> Entity {
+    commands
+        .spawn((
+            TransformBundle::from(Transform::from_xyz(pos.x, pos.y, 0.0)),
+            Collider::cuboid(size.x, size.y),
+            Sensor,
+            CollisionGroups::new(
+                // G1 is Scorch, G2 is embers, G3 is blocks, G4 is enemies, G5 is enemy_projectiles, G6 is triggers, G7 is water, G8 is debris
+                Group::GROUP_6,
+                Group::GROUP_1,
+            ),
+            ActiveEvents::COLLISION_EVENTS,
+            trigger,
+            LevelEntity,
+            StateScoped(AppState::InGame),
+        ))
+        .id()
+}
+
+/// runs the actions of a trigger when scorch enters it
+fn trigger_system(
+    mut commands: Commands,
+    mut collision_events: EventReader<CollisionEvent>,
+    time: Res<Time>,
+    asset_server: Res<SceneAsset>,
+    mut lock: ResMut<CameraLock>,
+
+    mut trigger_query: Query<&mut Trigger>,
+    scorch_query: Query<(), With<Scorch>>,
+    mut block_query: Query<(&LevelId, &mut Temperature), With<BlockInfo>>,
+    hint_query: Query<Entity, With<HintText>>,
+) {
+    for c_event in collision_events.read() {
+        let CollisionEvent::Started(e1, e2, _) = c_event else {
+            continue;
+        };
+        // either one could be the trigger
+        let (t_ent, other) = if trigger_query.contains(*e1) { (*e1, *e2) } else { (*e2, *e1) };
+        if !scorch_query.contains(other) {
+            continue;
+        }
+        let Ok(mut trigger) = trigger_query.get_mut(t_ent) else {
+            continue;
+        };
+        if trigger.once && trigger.fired {
+            continue;
+        }
+        trigger.fired = true;
+
+        for action in &trigger.actions {
+            match action {
+                TriggerAction::SpawnEnemies { enemies } => {
+                    for enemy in enemies {
+                        let e_ent = spawn_enemy(
+                            &mut commands,
+                            Vec2::from(enemy.pos),
+                            enemy.e_info.to_enemy_info(enemy.size),
+                            enemy.size,
+                            &asset_server,
+                        );
+                        commands.entity(e_ent).insert(LevelEntity);
+                    }
+                }
+                TriggerAction::Hint { text, duration } => {
+                    // only one hint at a time
+                    for entity in hint_query.iter() {
+                        commands.entity(entity).despawn_recursive();
+                    }
+                    spawn_hint(&mut commands, text, time.elapsed_seconds() + duration);
+                }
+                TriggerAction::LockCamera { pos } => lock.pos = Some(Vec2::from(*pos)),
+                TriggerAction::UnlockCamera => lock.pos = None,
+                TriggerAction::Ignite { blocks } => {
+                    // as hot as a fire, they catch the same as if one was next to them
+                    for (id, mut b_temp) in block_query.iter_mut() {
+                        if blocks.contains(&id.0) {
+                            b_temp.heat_to(FLAME_TEMP);
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
+
+fn spawn_hint(
+    commands: &mut Commands,
+    text: &str,
+    until: f32,
+) {
+    commands.spawn((
+        TextBundle::from_section(
+            text,
+            TextStyle {
+                font_size: 30.0,
+                color: Color::WHITE,
+                ..default()
+            },
+        )
+        .with_text_justify(JustifyText::Center)
+        .with_style(Style {
+            position_type: PositionType::Absolute,
+            top: Val::Px(40.0),
+            width: Val::Percent(100.0),
+            ..default()
+        }),
+        HintText { until },
+        StateScoped(AppState::InGame),
+    ));
+}
+
+fn hint_timeout(
+    mut commands: Commands,
+    time: Res<Time>,
+    hint_query: Query<(Entity, &HintText)>,
+) {
+    for (entity, hint) in hint_query.iter() {
+        if time.elapsed_seconds() >= hint.until {
+            commands.entity(entity).despawn_recursive();
+        }
+    }
+}
+
+/// a lock should not carry over to the menus or the editor
+fn unlock_camera(
+    mut lock: ResMut<CameraLock>,
+) {
+    lock.pos = None;
+}
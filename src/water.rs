@@ -0,0 +1,244 @@
+use bevy::prelude::*;
+use bevy_rapier2d::prelude::*;
+use rand::Rng;
+
+use std::collections::HashSet;
+
+// elsewhere in the project
+use crate::{
+    asset_loader::SceneAsset,
+    blocks::BlockInfo,
+    ember::EmberComponent,
+    enemies::EnemyInfo,
+    rng::RngResource,
+    scorch::Scorch,
+    sd_to_game::LevelEntity,
+    state_system::AppState,
+};
+
+// Water volumes and rain regions from the level file, the opposite of the fire pools.
+// They are sensors in G7, anything they overlap is found through the rapier context every frame:
+// scorch loses flame while in them, embers are put out and burning blocks stop burning.
+// Blocks, enemies and scorch in them are Wet, that cools them down (see heat.rs) and blocks get put out every frame,
+// so fire can not spread through water. They stay wet for a bit after they come out.
+// Rain is weaker than standing water, it also drops some rain so it can be seen.
+
+#[derive(Bundle)]
+pub struct WaterBundle {
+    pub water: Water,
+}
+
+pub struct WaterPlugin;
+
+impl Plugin for WaterPlugin {
+    fn build(&self, app: &mut App) {
+        app
+            .add_systems(
+                Update,
+                (water_system, spawn_rain_drops, fall_rain_drops).run_if(in_state(AppState::InGame))
+            )
+        ;
+    }
+}
+
+/// the colors water and rain are drawn with, the editor uses them too
+pub const WATER_COLOR: Color = Color::srgba(0.1, 0.3, 1.0, 0.5);
+pub const RAIN_COLOR: Color = Color::srgba(0.5, 0.6, 1.0, 0.15);
+
+/// flame per second scorch loses in water
+const WATER_DRAIN: f32 = 30.0;
+/// flame per second scorch loses in rain
+const RAIN_DRAIN: f32 = 8.0;
+/// how long things stay wet once they are out of the water
+const DRY_TIME: f32 = 1.5;
+/// how many rain drops per second for every 100x100 of rain
+const RAIN_DENSITY: f32 = 2.0;
+/// how fast the rain drops fall
+const RAIN_SPEED: f32 = 800.0;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum WaterKind {
+    Water,
+    Rain,
+}
+
+/// water or rain, size is half the width and height
+#[derive(Component, Debug, Clone, Copy)]
+pub struct Water {
+    pub kind: WaterKind,
+    pub size: Vec2,
+}
+
+impl Water {
+    /// flame per second scorch loses in it
+    pub fn drain(&self) -> f32 {
+        match self.kind {
+            WaterKind::Water => WATER_DRAIN,
+            WaterKind::Rain => RAIN_DRAIN,
+        }
+    }
+}
+
+/// on anything in water or rain, blocks are put out as soon as they catch fire and nothing wet can catch.
+/// drying is how long until it is dry, it only counts down once it is out of the water
+#[derive(Component, Debug, Clone, Copy)]
+pub struct Wet {
+    pub drying: f32,
+}
+
+/// only for looks, falls to the bottom of the rain then goes away
+#[derive(Component)]
+struct RainDrop {
+    bottom: f32,
+}
+
+/// spawns water or rain, size is half the width and height
+pub fn spawn_water(
+    commands: &mut Commands,
+    pos: Vec2,
+    size: Vec2,
+    kind: WaterKind,
+    asset_server: &Res<SceneAsset>,
+) -> Entity {
+    commands
+        .spawn((
+            SpriteBundle {
+                texture: asset_server.t_temp.clone(),
+                // in front of scorch, same as the fire pools
+                transform: Transform::from_xyz(pos.x, pos.y, 0.5),
+                sprite: Sprite {
+                    custom_size: Some(size * 2.0),
+                    color: if kind == WaterKind::Water { WATER_COLOR } else { RAIN_COLOR },
+                    ..default()
+                },
+                ..Default::default()
+            },
+            Collider::cuboid(size.x, size.y),
+            Sensor,
+            CollisionGroups::new(
+                // G1 is Scorch, G2 is embers, G3 is blocks, G4 is enemies, G5 is enemy_projectiles, G6 is triggers, G7 is water, G8 is debris
+                Group::GROUP_7,
+                Group::GROUP_1 | Group::GROUP_2 | Group::GROUP_3 | Group::GROUP_4,
+            ),
+            // blocks are fixed too, without this rapier never checks them against the water
+            ActiveCollisionTypes::default() | ActiveCollisionTypes::STATIC_STATIC,
+            Water { kind, size },
+            LevelEntity,
+            StateScoped(AppState::InGame),
+        ))
+        .id()
+}
+
+/// drains scorch, puts out embers and blocks, and keeps track of what is wet
+#[allow(clippy::too_many_arguments)]
+fn water_system(
+    mut commands: Commands,
+    time: Res<Time>,
+    rc: Res<RapierContext>,
+    water_query: Query<(Entity, &Water)>,
+    mut s_query: Query<&mut Scorch>,
+    ember_query: Query<(), With<EmberComponent>>,
+    mut block_query: Query<&mut BlockInfo>,
+    enemy_query: Query<(), With<EnemyInfo>>,
+    mut wet_query: Query<(Entity, &mut Wet)>,
+) {
+    let mut wet = HashSet::new();
+    for (w_ent, water) in water_query.iter() {
+        for (e1, e2, intersecting) in rc.intersection_pairs_with(w_ent) {
+            if !intersecting {
+                continue;
+            }
+            let other = if e1 == w_ent { e2 } else { e1 };
+
+            if let Ok(mut s_info) = s_query.get_mut(other) {
+                s_info.unblockable_dmg(water.drain() * time.delta_seconds());
+                wet.insert(other);
+            } else if ember_query.contains(other) {
+                commands.entity(other).despawn();
+            } else if let Ok(mut b_info) = block_query.get_mut(other) {
+                // even blocks scorch can not put out, nothing burns under water
+                if b_info.is_burning() || b_info.burn.is_smouldering() {
+                    b_info.douse();
+                }
+                wet.insert(other);
+            } else if enemy_query.contains(other) {
+                wet.insert(other);
+            }
+        }
+    }
+
+    // anything still in the water stays soaked, the rest dries off
+    for (entity, mut is_wet) in wet_query.iter_mut() {
+        if wet.remove(&entity) {
+            is_wet.drying = DRY_TIME;
+        } else {
+            is_wet.drying -= time.delta_seconds();
+            if is_wet.drying <= 0.0 {
+                commands.entity(entity).remove::<Wet>();
+            }
+        }
+    }
+    // what is left just got wet
+    for entity in wet {
+        commands.entity(entity).insert(Wet { drying: DRY_TIME });
+    }
+}
+
+fn spawn_rain_drops(
+    mut commands: Commands,
+    time: Res<Time>,
+    mut rng: ResMut<RngResource>,
+    asset_server: Res<SceneAsset>,
+    water_query: Query<(&Water, &Transform)>,
+) {
+    for (water, trans) in water_query.iter() {
+        if water.kind != WaterKind::Rain {
+            continue;
+        }
+        let area = water.size.x * water.size.y * 4.0 / 10000.0;
+        let expected = RAIN_DENSITY * area * time.delta_seconds();
+        // the part of a drop left over is a chance of one more
+        let count = expected as u32 + rng.rng.gen_bool(expected.fract() as f64) as u32;
+        let center = trans.translation.truncate();
+        let (top, bottom) = (center.y + water.size.y, center.y - water.size.y);
+        for _ in 0..count {
+            let x = rng.rng.gen_range((center.x - water.size.x)..=(center.x + water.size.x));
+            spawn_rain_drop(&mut commands, &asset_server, Vec2::new(x, top), bottom);
+        }
+    }
+}
+
+fn spawn_rain_drop(
+    commands: &mut Commands,
+    asset_server: &Res<SceneAsset>,
+    pos: Vec2,
+    bottom: f32,
+) {
+    commands.spawn((
+        SpriteBundle {
+            texture: asset_server.t_temp.clone(),
+            transform: Transform::from_xyz(pos.x, pos.y, 0.5),
+            sprite: Sprite {
+                custom_size: Some(Vec2::new(4.0, 30.0)),
+                color: WATER_COLOR,
+                ..default()
+            },
+            ..Default::default()
+        },
+        RainDrop { bottom },
+        StateScoped(AppState::InGame),
+    ));
+}
+
+fn fall_rain_drops(
+    mut commands: Commands,
+    time: Res<Time>,
+    mut drop_query: Query<(Entity, &RainDrop, &mut Transform)>,
+) {
+    for (entity, drop, mut trans) in drop_query.iter_mut() {
+        trans.translation.y -= RAIN_SPEED * time.delta_seconds();
+        if trans.translation.y < drop.bottom {
+            commands.entity(entity).despawn();
+        }
+    }
+}